@@ -0,0 +1,234 @@
+use ark_ec::{short_weierstrass::{SWCurveConfig, Affine, Projective}, CurveGroup};
+use ark_ff::{One, Zero};
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+use anyhow::{anyhow, bail, Result};
+
+use crate::curves::Curve;
+use crate::pairing::{Coordinates, ProjectivePoint, point_from_sample};
+use crate::report::{Outcome, Report};
+use crate::tower::{Constant, FromFieldValue};
+use crate::validate;
+
+/*
+enum curve_operation_test_points : std::size_t {
+    p1,
+    p2,
+    p1_plus_p2,
+    p1_minus_p2,
+    p1_mul_C1,
+    p2_mul_C1_plus_p2_mul_C2,
+    p1_dbl,
+    p1_mixed_add_p2,
+    p1_to_affine,
+    p2_to_special
+};
+*/
+
+/// Groups of checks run over a group-law vector.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Suite {
+    /// p1+p2, p1-p2
+    Add,
+    /// p1*c1, p2*c1 + p2*c2
+    Mul,
+    /// p1+p1
+    Dbl,
+    /// p1 + affine p2, in mixed coordinates
+    MixedAdd,
+    /// p1 and p2 in their Z = 1 forms
+    Normalize,
+}
+
+/// Group a group-law vector holds points of.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Group {
+    G1,
+    G2,
+}
+
+impl Group {
+    pub const ALL: [Group; 2] = [Group::G1, Group::G2];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Group::G1 => "g1",
+            Group::G2 => "g2",
+        }
+    }
+}
+
+/* curve and group are optional, detected from the points when absent */
+#[derive(Deserialize, Serialize, Debug)]
+pub struct TestSample {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub curve: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<Group>,
+    /* jacobian when absent */
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coordinates: Option<Coordinates>,
+    pub constants: Vec<Constant>,
+    pub point_coordinates: Vec<ProjectivePoint>,
+}
+
+#[derive(Debug)]
+struct TestData<P:SWCurveConfig> {
+    pub c1: P::ScalarField,
+    pub c2: P::ScalarField,
+    pub p1: Projective<P>,
+    pub p2: Projective<P>,
+    pub p1_plus_p2: Projective<P>,
+    pub p1_minus_p2: Projective<P>,
+    pub p1_mul_c1: Projective<P>,
+    pub p2_mul_c1_plus_p2_mul_c2: Projective<P>,
+    pub p1_dbl: Projective<P>,
+    /* left out of the older vectors, which stop at p1_dbl */
+    pub p1_mixed_add_p2: Option<Projective<P>>,
+    pub p1_to_affine: Option<Projective<P>>,
+    pub p2_to_special: Option<Projective<P>>,
+}
+
+impl<P> TestData<P>
+where
+    P: SWCurveConfig,
+    P::BaseField: FromFieldValue,
+{
+    fn from(sample: &TestSample) -> Result<Self> {
+        let coords = sample.coordinates.unwrap_or_default();
+        let point = |i: usize| {
+            let x = sample.point_coordinates.get(i).ok_or_else(|| anyhow!(
+                "$.point_coordinates: expected at least 7 points, got {}", sample.point_coordinates.len()))?;
+            point_from_sample(x, coords, &format!("$.point_coordinates[{i}]"))
+        };
+        let constant = |i: usize| sample.constants.get(i)
+            .ok_or_else(|| anyhow!("$.constants: expected 2 constants, got {}", sample.constants.len()))?
+            .to_field(&format!("$.constants[{i}]"));
+        let optional = |i: usize| (i < sample.point_coordinates.len()).then(|| point(i)).transpose();
+        Ok(TestData {
+            c1: constant(0)?,
+            c2: constant(1)?,
+            p1 : point(0)?,
+            p2 : point(1)?,
+            p1_plus_p2        : point(2)?,
+            p1_minus_p2       : point(3)?,
+            p1_mul_c1                : point(4)?,
+            p2_mul_c1_plus_p2_mul_c2 : point(5)?,
+            p1_dbl                   : point(6)?,
+            p1_mixed_add_p2          : optional(7)?,
+            p1_to_affine             : optional(8)?,
+            p2_to_special            : optional(9)?,
+        })
+    }
+}
+
+fn run_test_case<P: SWCurveConfig>(data: &TestData<P>, suites: &[Suite], report: &mut Report) {
+    let enabled = |s: Suite| suites.is_empty() || suites.contains(&s);
+
+    if enabled(Suite::Add) {
+        report.check_eq("p1_plus_p2",  data.p1+data.p2, data.p1_plus_p2);
+        report.check_eq("p1_minus_p2", data.p1-data.p2, data.p1_minus_p2);
+    }
+    if enabled(Suite::Mul) {
+        report.check_eq("p1_mul_C1", data.p1*data.c1, data.p1_mul_c1);
+        report.check_eq("p2_mul_C1_plus_p2_mul_C2",
+            data.p2*data.c1 + data.p2*data.c2, data.p2_mul_c1_plus_p2_mul_c2);
+    }
+    if enabled(Suite::Dbl) {
+        report.check_eq("p1_dbl", data.p1 + data.p1, data.p1_dbl);
+    }
+    if enabled(Suite::MixedAdd) {
+        if let Some(x) = data.p1_mixed_add_p2 {
+            check_mixed_add(report, data.p1, data.p2, x);
+        }
+    }
+    if enabled(Suite::Normalize) {
+        if let Some(x) = data.p1_to_affine {
+            check_normalized(report, "p1_to_affine", data.p1, x);
+        }
+        if let Some(x) = data.p2_to_special {
+            check_normalized(report, "p2_to_special", data.p2, x);
+        }
+    }
+}
+
+/// Checks `x` against p1 + p2 with p2 taken in affine coordinates. A mixed
+/// addition handed the X, Y of a p2 that was never normalized is the usual
+/// way to get this wrong, so that result is named in the diagnostic.
+fn check_mixed_add<P: SWCurveConfig>(report: &mut Report, p1: Projective<P>, p2: Projective<P>, x: Projective<P>) {
+    let expected = p1 + p2.into_affine();
+    if expected != x && !p2.is_zero() && p1 + Affine::new_unchecked(p2.x, p2.y) == x {
+        report.record("p1_mixed_add_p2", Outcome::Failed {
+            message: "equals p1 plus the projective X, Y of p2, p2 was not normalized to affine".to_string(),
+        });
+    } else {
+        report.check_eq("p1_mixed_add_p2", expected, x);
+    }
+}
+
+/// Checks that `x` is `p` in the Z = 1 form, the form to_affine and
+/// to_special leave a nonzero point in.
+fn check_normalized<P: SWCurveConfig>(report: &mut Report, name: &str, p: Projective<P>, x: Projective<P>) {
+    if p == x && !p.is_zero() && !x.z.is_one() {
+        report.record(name, Outcome::Failed { message: format!("equals the expected point, but Z = {} instead of 1", x.z) });
+    } else {
+        report.check_eq(name, p, x);
+    }
+}
+
+/// Whether the input points p1 and p2 of the sample lie on `C`. Results
+/// are left out, as a wrong one must fail its check rather than detection.
+pub fn points_on_curve<C>(sample: &TestSample) -> bool
+where
+    C: SWCurveConfig,
+    C::BaseField: FromFieldValue,
+{
+    sample.point_coordinates.len() >= 2 && sample.point_coordinates[..2].iter().all(|p| {
+        point_from_sample::<C>(p, sample.coordinates.unwrap_or_default(), "$.point_coordinates")
+            .map(|p| p.into_affine().is_on_curve())
+            .unwrap_or(false)
+    })
+}
+
+/// Picks the curve and group of a sample: the ones given on the command
+/// line or in the file, otherwise the first ones its input points lie on.
+pub fn detect(sample: &TestSample, curve: Option<Curve>, group: Option<Group>) -> Result<(Curve, Group)> {
+    let curve = match (curve, &sample.curve) {
+        (Some(curve), _) => Some(curve),
+        (None, Some(name)) => Some(Curve::from_name(name)?),
+        (None, None) => None,
+    };
+    let group = group.or(sample.group);
+    let curves = curve.map_or(Curve::ALL.to_vec(), |c| vec![c]);
+    let groups = group.map_or(Group::ALL.to_vec(), |g| vec![g]);
+
+    for curve in &curves {
+        for group in &groups {
+            if curve.group_on_curve(*group, sample) {
+                return Ok((*curve, *group));
+            }
+        }
+    }
+    match (curve, group) {
+        (Some(curve), Some(group)) => Ok((curve, group)),
+        _ => bail!("points do not belong to any known curve"),
+    }
+}
+
+/// Runs the group-law checks over one sample of `C`.
+pub fn test_sample<C>(sample: &TestSample, suites: &[Suite], report: &mut Report) -> Result<()>
+where
+    C: SWCurveConfig,
+    C::BaseField: FromFieldValue,
+{
+    let data = TestData::<C>::from(sample)?;
+    validate::check_inputs(report, [
+        ("$.point_coordinates[0]".to_string(), validate::validity(&data.p1)),
+        ("$.point_coordinates[1]".to_string(), validate::validity(&data.p2)),
+    ]);
+    run_test_case(&data, suites, report);
+    Ok(())
+}
@@ -0,0 +1,445 @@
+use ark_ec::{
+    pairing::Pairing,
+    bls12::{self, Bls12, Bls12Config},
+    bn::{self, Bn, BnConfig},
+    mnt4::{self, MNT4, MNT4Config},
+    mnt6::{self, MNT6, MNT6Config},
+    short_weierstrass::{SWCurveConfig, Projective, Affine},
+    CurveConfig, CurveGroup};
+
+use anyhow::{anyhow, bail, Result};
+
+use ark_ff::{Fp2, Fp3, Fp6, Fp6Config};
+use ark_std::rand::Rng;
+
+use crate::curve_ops::{self, Group};
+use crate::cyclotomic;
+use crate::diff::tower_powers;
+use crate::field_ops::{self, Field};
+use crate::generate::{self, Kind};
+use crate::groth16;
+use crate::pairing::{self, TestSample, Coordinates, ProjectivePoint, Suite, point_from_sample};
+use crate::properties;
+use crate::precomp::{
+    G1Precomputed, G2Precomputed, Layout, g2_precomputed_point,
+    check_g1_precomputed, check_g1_twist, check_ell_coeffs, check_ate_coeffs,
+    g1_precomputed, ell_precomputed, ate_precomputed,
+    bn_steps, bn_twist, bls12_steps, bls12_twist, mnt4_prepared, mnt6_prepared};
+use crate::report::Report;
+use crate::serialization;
+use crate::tower::{FromFieldValue, ToFieldValue};
+use crate::validate;
+
+type ScalarField<C> = <C as CurveConfig>::ScalarField;
+
+/// Pairing engine together with the knowledge of how its precomputations
+/// are laid out in the vector files. Points and GT elements are read with
+/// the field's [`FromFieldValue`].
+pub trait TestingCurve:
+    Pairing<
+        G1 = Projective<Self::G1Config>,
+        G2 = Projective<Self::G2Config>,
+        BaseField: FromFieldValue + ToFieldValue,
+        ScalarField: FromFieldValue + ToFieldValue,
+        TargetField: FromFieldValue + ToFieldValue,
+    >
+{
+    type G1Config: SWCurveConfig<ScalarField = Self::ScalarField, BaseField = Self::BaseField>;
+    type G2Config: SWCurveConfig<ScalarField = Self::ScalarField, BaseField: FromFieldValue + ToFieldValue>;
+
+    /// Whether arkworks pairs a point with the G2 identity; the MNT Miller
+    /// loops panic on it.
+    const PAIRS_G2_IDENTITY: bool;
+
+    /// Compares a precomputed G1 entry, found at `path`, with arkworks'
+    /// `G1Prepared` of `p`.
+    fn check_g1_precomputed(label: &str, path: &str, x: &G1Precomputed, p: Self::G1) -> Result<()>;
+
+    /// Compares a precomputed G2 entry, found at `path`, with arkworks'
+    /// `G2Prepared` of `q`.
+    fn check_g2_precomputed(label: &str, path: &str, x: &G2Precomputed, q: Self::G2) -> Result<()>;
+
+    /// Precomputed G1 entry of arkworks' `G1Prepared` of `p`.
+    fn g1_precomputed(p: Self::G1) -> G1Precomputed;
+    /// Precomputed G2 entry of arkworks' `G2Prepared` of `q`, line
+    /// coefficients written in `layout` where the curve has them.
+    fn g2_precomputed(q: Self::G2, layout: Layout) -> G2Precomputed;
+
+    /// Constants of GT that mismatching GT values are tested for being a
+    /// factor apart, see [`crate::diff::diff`].
+    fn gt_constants() -> Vec<(String, Self::TargetField)>;
+
+    /// Runs the field-arithmetic checks over a sample of `field`.
+    fn test_field(field: Field, sample: &field_ops::TestSample, suites: &[field_ops::Suite], report: &mut Report) -> Result<()>;
+
+    /// Field-arithmetic vector of random elements of `field`.
+    fn field_sample<R: Rng>(field: Field, rng: &mut R) -> Result<field_ops::TestSample>;
+}
+
+/// The field methods of [`TestingCurve`], over the fields of a curve given
+/// as `name => type, whether arkworks has square roots in it`.
+macro_rules! field_methods {
+    ($($field:ident => $ty:ty, $sqrt:expr;)*) => {
+        fn test_field(field: Field, sample: &field_ops::TestSample, suites: &[field_ops::Suite], report: &mut Report) -> Result<()> {
+            match field {
+                $(Field::$field => field_ops::test_sample::<$ty>(sample, suites, $sqrt, report),)*
+                _ => bail!("{} is not a field of this curve", field.name()),
+            }
+        }
+
+        fn field_sample<R: Rng>(field: Field, rng: &mut R) -> Result<field_ops::TestSample> {
+            match field {
+                $(Field::$field => Ok(generate::field_sample::<$ty>(rng, $sqrt)),)*
+                _ => bail!("{} is not a field of this curve", field.name()),
+            }
+        }
+    };
+}
+
+impl<P: BnConfig> TestingCurve for Bn<P>
+where
+    P::Fp: FromFieldValue + ToFieldValue,
+    ScalarField<P::G1Config>: FromFieldValue + ToFieldValue,
+{
+    type G1Config = P::G1Config;
+    type G2Config = P::G2Config;
+    const PAIRS_G2_IDENTITY: bool = true;
+
+    fn check_g1_precomputed(label: &str, path: &str, x: &G1Precomputed, p: Self::G1) -> Result<()> {
+        check_g1_precomputed(label, path, x, &p, &bn::G1Prepared::<P>::from(p).0)
+    }
+
+    fn check_g2_precomputed(label: &str, path: &str, x: &G2Precomputed, q: Self::G2) -> Result<()> {
+        let x = x.ell().map_err(|e| anyhow!("{path}: {e}"))?;
+        let steps = bn_steps::<P>(q.into_affine());
+        check_ell_coeffs(label, path, x, &steps, bn_twist::<P>(), P::Fp6Config::NONRESIDUE)
+    }
+
+    fn g1_precomputed(p: Self::G1) -> G1Precomputed {
+        g1_precomputed(&bn::G1Prepared::<P>::from(p).0)
+    }
+
+    fn g2_precomputed(q: Self::G2, layout: Layout) -> G2Precomputed {
+        let q = q.into_affine();
+        let steps = bn_steps::<P>(q);
+        G2Precomputed::Ell(ell_precomputed((q.x, q.y), &steps, bn_twist::<P>(), P::Fp6Config::NONRESIDUE, layout))
+    }
+
+    fn gt_constants() -> Vec<(String, Self::TargetField)> {
+        tower_powers()
+    }
+
+    field_methods! {
+        Fr   => Self::ScalarField, true;
+        Fq   => P::Fp, true;
+        Fq2  => Fp2<P::Fp2Config>, true;
+        Fq6  => Fp6<P::Fp6Config>, false;
+        Fq12 => Self::TargetField, false;
+    }
+}
+
+impl<P: Bls12Config> TestingCurve for Bls12<P>
+where
+    P::Fp: FromFieldValue + ToFieldValue,
+    ScalarField<P::G1Config>: FromFieldValue + ToFieldValue,
+{
+    type G1Config = P::G1Config;
+    type G2Config = P::G2Config;
+    const PAIRS_G2_IDENTITY: bool = true;
+
+    fn check_g1_precomputed(label: &str, path: &str, x: &G1Precomputed, p: Self::G1) -> Result<()> {
+        check_g1_precomputed(label, path, x, &p, &bls12::G1Prepared::<P>::from(p).0)
+    }
+
+    fn check_g2_precomputed(label: &str, path: &str, x: &G2Precomputed, q: Self::G2) -> Result<()> {
+        let x = x.ell().map_err(|e| anyhow!("{path}: {e}"))?;
+        let steps = bls12_steps::<P>(q.into_affine());
+        check_ell_coeffs(label, path, x, &steps, bls12_twist::<P>(), P::Fp6Config::NONRESIDUE)
+    }
+
+    fn g1_precomputed(p: Self::G1) -> G1Precomputed {
+        g1_precomputed(&bls12::G1Prepared::<P>::from(p).0)
+    }
+
+    fn g2_precomputed(q: Self::G2, layout: Layout) -> G2Precomputed {
+        let q = q.into_affine();
+        let steps = bls12_steps::<P>(q);
+        G2Precomputed::Ell(ell_precomputed((q.x, q.y), &steps, bls12_twist::<P>(), P::Fp6Config::NONRESIDUE, layout))
+    }
+
+    fn gt_constants() -> Vec<(String, Self::TargetField)> {
+        tower_powers()
+    }
+
+    field_methods! {
+        Fr   => Self::ScalarField, true;
+        Fq   => P::Fp, true;
+        Fq2  => Fp2<P::Fp2Config>, true;
+        Fq6  => Fp6<P::Fp6Config>, false;
+        Fq12 => Self::TargetField, false;
+    }
+}
+
+/* MNT4 G2 lives over Fq2 like the Bn/Bls12 twists, but GT is Fq4 */
+impl<P: MNT4Config> TestingCurve for MNT4<P>
+where
+    P::Fp: FromFieldValue + ToFieldValue,
+    ScalarField<P::G1Config>: FromFieldValue + ToFieldValue,
+{
+    type G1Config = P::G1Config;
+    type G2Config = P::G2Config;
+    const PAIRS_G2_IDENTITY: bool = false;
+
+    fn check_g1_precomputed(label: &str, path: &str, x: &G1Precomputed, p: Self::G1) -> Result<()> {
+        let prepared = mnt4::G1Prepared::<P>::from(p);
+        check_g1_precomputed(label, path, x, &p, &Affine::new_unchecked(prepared.x, prepared.y))?;
+        check_g1_twist(label, path, x, prepared.x_twist, prepared.y_twist)
+    }
+
+    fn check_g2_precomputed(label: &str, path: &str, x: &G2Precomputed, q: Self::G2) -> Result<()> {
+        let x = x.ate().map_err(|e| anyhow!("{path}: {e}"))?;
+        check_ate_coeffs(label, path, x, &mnt4_prepared::<P>(q.into_affine()))
+    }
+
+    fn g1_precomputed(p: Self::G1) -> G1Precomputed {
+        let prepared = mnt4::G1Prepared::<P>::from(p);
+        G1Precomputed {
+            px_twist: Some(prepared.x_twist.to_value()),
+            py_twist: Some(prepared.y_twist.to_value()),
+            ..g1_precomputed(&Affine::<P::G1Config>::new_unchecked(prepared.x, prepared.y))
+        }
+    }
+
+    fn g2_precomputed(q: Self::G2, _: Layout) -> G2Precomputed {
+        G2Precomputed::Ate(ate_precomputed(&mnt4_prepared::<P>(q.into_affine())))
+    }
+
+    fn gt_constants() -> Vec<(String, Self::TargetField)> {
+        tower_powers()
+    }
+
+    field_methods! {
+        Fr  => Self::ScalarField, true;
+        Fq  => P::Fp, true;
+        Fq2 => Fp2<P::Fp2Config>, true;
+        Fq4 => Self::TargetField, true;
+    }
+}
+
+/* MNT6 G2 lives over Fq3 and GT is Fq6 = Fq3^2 */
+impl<P: MNT6Config> TestingCurve for MNT6<P>
+where
+    P::Fp: FromFieldValue + ToFieldValue,
+    ScalarField<P::G1Config>: FromFieldValue + ToFieldValue,
+{
+    type G1Config = P::G1Config;
+    type G2Config = P::G2Config;
+    const PAIRS_G2_IDENTITY: bool = false;
+
+    fn check_g1_precomputed(label: &str, path: &str, x: &G1Precomputed, p: Self::G1) -> Result<()> {
+        let prepared = mnt6::G1Prepared::<P>::from(p);
+        check_g1_precomputed(label, path, x, &p, &Affine::new_unchecked(prepared.x, prepared.y))?;
+        check_g1_twist(label, path, x, prepared.x_twist, prepared.y_twist)
+    }
+
+    fn check_g2_precomputed(label: &str, path: &str, x: &G2Precomputed, q: Self::G2) -> Result<()> {
+        let x = x.ate().map_err(|e| anyhow!("{path}: {e}"))?;
+        check_ate_coeffs(label, path, x, &mnt6_prepared::<P>(q.into_affine()))
+    }
+
+    fn g1_precomputed(p: Self::G1) -> G1Precomputed {
+        let prepared = mnt6::G1Prepared::<P>::from(p);
+        G1Precomputed {
+            px_twist: Some(prepared.x_twist.to_value()),
+            py_twist: Some(prepared.y_twist.to_value()),
+            ..g1_precomputed(&Affine::<P::G1Config>::new_unchecked(prepared.x, prepared.y))
+        }
+    }
+
+    fn g2_precomputed(q: Self::G2, _: Layout) -> G2Precomputed {
+        G2Precomputed::Ate(ate_precomputed(&mnt6_prepared::<P>(q.into_affine())))
+    }
+
+    fn gt_constants() -> Vec<(String, Self::TargetField)> {
+        tower_powers()
+    }
+
+    field_methods! {
+        Fr  => Self::ScalarField, true;
+        Fq  => P::Fp, true;
+        Fq3 => Fp3<P::Fp3Config>, true;
+        Fq6 => Self::TargetField, true;
+    }
+}
+
+/// Calls a function generic over `P: TestingCurve` with the arkworks
+/// engine matching a runtime [`Curve`].
+macro_rules! dispatch {
+    ($curve:expr, $($f:ident)::+ ( $($arg:expr),* )) => {
+        match $curve {
+            Curve::Bn254     => $($f)::+::<ark_bn254::Bn254>($($arg),*),
+            Curve::Bls12_377 => $($f)::+::<ark_bls12_377::Bls12_377>($($arg),*),
+            Curve::Bls12_381 => $($f)::+::<ark_bls12_381::Bls12_381>($($arg),*),
+            Curve::Mnt4_298  => $($f)::+::<ark_mnt4_298::MNT4_298>($($arg),*),
+            Curve::Mnt6_298  => $($f)::+::<ark_mnt6_298::MNT6_298>($($arg),*),
+        }
+    };
+}
+
+/// Curves known to the harness.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Curve {
+    Bn254,
+    Bls12_377,
+    Bls12_381,
+    Mnt4_298,
+    Mnt6_298,
+}
+
+impl Curve {
+    pub const ALL: [Curve; 5] = [
+        Curve::Bn254,
+        Curve::Bls12_377,
+        Curve::Bls12_381,
+        Curve::Mnt4_298,
+        Curve::Mnt6_298,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Curve::Bn254     => "bn254",
+            Curve::Bls12_377 => "bls12_377",
+            Curve::Bls12_381 => "bls12_381",
+            Curve::Mnt4_298  => "mnt4_298",
+            Curve::Mnt6_298  => "mnt6_298",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Result<Self> {
+        let name = match name.to_lowercase().replace('-', "_").as_str() {
+            "bn128" | "alt_bn128" => "bn254".to_string(),
+            other => other.to_string(),
+        };
+        Self::ALL.into_iter()
+            .find(|c| c.name() == name)
+            .ok_or_else(|| anyhow!("unknown curve '{name}'"))
+    }
+
+    /// Picks the curve a pairing sample was produced for: either the one
+    /// named in its `curve` field, or the first curve on which the sample's
+    /// G1 points actually lie.
+    pub fn detect(sample: &TestSample) -> Result<Self> {
+        if let Some(name) = &sample.curve {
+            return Self::from_name(name);
+        }
+        Self::ALL.into_iter()
+            .find(|c| c.g1_on_curve(sample))
+            .ok_or_else(|| anyhow!("G1 points do not belong to any known curve"))
+    }
+
+    fn g1_on_curve(&self, sample: &TestSample) -> bool {
+        fn check<P: TestingCurve>(sample: &TestSample) -> bool {
+            !sample.g1.is_empty() && sample.g1.iter().all(|p| {
+                point_from_sample::<P::G1Config>(p, sample.coordinates.unwrap_or_default(), "$.G1")
+                    .map(|p| p.into_affine().is_on_curve())
+                    .unwrap_or(false)
+            })
+        }
+        dispatch!(self, check(sample))
+    }
+
+    /// Loads the pairing sample for this curve and runs the checks on it.
+    pub fn test_pairing(&self, sample: &TestSample, suites: &[Suite], report: &mut Report) -> Result<()> {
+        dispatch!(self, pairing::test_sample(sample, suites, report))
+    }
+
+    /// Whether the input points of a group-law sample lie on `group` of
+    /// this curve.
+    pub fn group_on_curve(&self, group: Group, sample: &curve_ops::TestSample) -> bool {
+        fn check<P: TestingCurve>(group: Group, sample: &curve_ops::TestSample) -> bool {
+            match group {
+                Group::G1 => curve_ops::points_on_curve::<P::G1Config>(sample),
+                Group::G2 => curve_ops::points_on_curve::<P::G2Config>(sample),
+            }
+        }
+        dispatch!(self, check(group, sample))
+    }
+
+    /// Runs the group-law checks over a sample of `group` of this curve.
+    pub fn test_group_law(&self, group: Group, sample: &curve_ops::TestSample, suites: &[curve_ops::Suite], report: &mut Report) -> Result<()> {
+        fn run<P: TestingCurve>(group: Group, sample: &curve_ops::TestSample, suites: &[curve_ops::Suite], report: &mut Report) -> Result<()> {
+            match group {
+                Group::G1 => curve_ops::test_sample::<P::G1Config>(sample, suites, report),
+                Group::G2 => curve_ops::test_sample::<P::G2Config>(sample, suites, report),
+            }
+        }
+        dispatch!(self, run(group, sample, suites, report))
+    }
+
+    /// Checks the verdicts of a validation sample over `group` of this curve.
+    pub fn test_validation(&self, group: Group, sample: &validate::TestSample, report: &mut Report) -> Result<()> {
+        fn run<P: TestingCurve>(group: Group, sample: &validate::TestSample, report: &mut Report) -> Result<()> {
+            match group {
+                Group::G1 => validate::test_sample::<P::G1Config>(sample, report),
+                Group::G2 => validate::test_sample::<P::G2Config>(sample, report),
+            }
+        }
+        dispatch!(self, run(group, sample, report))
+    }
+
+    /// Checks the point encodings of a serialization sample over `group`
+    /// of this curve.
+    pub fn test_serialization(&self, group: Group, sample: &serialization::TestSample, suites: &[serialization::Suite], report: &mut Report) -> Result<()> {
+        fn run<P: TestingCurve>(group: Group, sample: &serialization::TestSample, suites: &[serialization::Suite], zcash: bool, report: &mut Report) -> Result<()> {
+            match group {
+                Group::G1 => serialization::test_sample::<P::G1Config>(sample, suites, zcash, report),
+                Group::G2 => serialization::test_sample::<P::G2Config>(sample, suites, zcash, report),
+            }
+        }
+        let zcash = *self == Curve::Bls12_381;
+        dispatch!(self, run(group, sample, suites, zcash, report))
+    }
+
+    /// Runs the field-arithmetic checks over a sample of `field` of this
+    /// curve.
+    pub fn test_field(&self, field: Field, sample: &field_ops::TestSample, suites: &[field_ops::Suite], report: &mut Report) -> Result<()> {
+        fn run<P: TestingCurve>(field: Field, sample: &field_ops::TestSample, suites: &[field_ops::Suite], report: &mut Report) -> Result<()> {
+            P::test_field(field, sample, suites, report)
+        }
+        dispatch!(self, run(field, sample, suites, report))
+    }
+
+    /// Runs the cyclotomic checks over a sample of GT elements of this curve.
+    pub fn test_cyclotomic(&self, sample: &cyclotomic::TestSample, suites: &[cyclotomic::Suite], report: &mut Report) -> Result<()> {
+        dispatch!(self, cyclotomic::test_sample(sample, suites, report))
+    }
+
+    /// Checks the pairing properties over random points drawn from `seed`.
+    pub fn test_properties(&self, seed: u64, rounds: usize, report: &mut Report) {
+        dispatch!(self, properties::test_random(seed, rounds, report))
+    }
+
+    /// Checks the Groth16 proof of a sample over this curve.
+    pub fn test_groth16(&self, sample: &groth16::TestSample, report: &mut Report) -> Result<()> {
+        dispatch!(self, groth16::test_sample(sample, report))
+    }
+
+    /// Generates a sample of `kind` from `seed`, as a JSON value.
+    pub fn generate(&self, kind: Kind, seed: u64, layout: Layout, coords: Coordinates, field: Field) -> Result<serde_json::Value> {
+        dispatch!(self, generate::generate(*self, kind, seed, layout, coords, field))
+    }
+
+    /// Checks a standalone G2 precomputation. Entries without coordinates
+    /// are checked against `point`, written in the given coordinates, instead.
+    pub fn test_g2_precomputed(&self, x: &G2Precomputed, point: Option<(&ProjectivePoint, Coordinates)>) -> Result<()> {
+        fn run<P: TestingCurve>(x: &G2Precomputed, point: Option<(&ProjectivePoint, Coordinates)>) -> Result<()> {
+            let q = match (x.coordinates(), point) {
+                (Some(_), _) => g2_precomputed_point::<P::G2Config>("$", x)?.into(),
+                (None, Some((point, coords))) => point_from_sample(point, coords, "--point-from $.G2[0]")?,
+                (None, None) => bail!("precomputed G2 entry has no coordinates, pass the point it was made for"),
+            };
+            P::check_g2_precomputed("g2", "$", x, q)
+        }
+        dispatch!(self, run(x, point))
+    }
+}
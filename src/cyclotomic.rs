@@ -0,0 +1,120 @@
+use ark_ff::{CyclotomicMultSubgroup, Field, One, PrimeField};
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+use anyhow::Result;
+
+use crate::curves::TestingCurve;
+use crate::diff::check_field_eq;
+use crate::report::{Outcome, Report};
+use crate::tower::{Constant, FieldValue, FromFieldValue};
+
+/// Groups of checks run over a cyclotomic vector.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Suite {
+    /// cyclotomic_square(a)
+    Square,
+    /// cyclotomic_exp(a, e)
+    Exp,
+    /// cyclotomic_inverse(a), the conjugate
+    Inverse,
+    /// a^r = 1
+    Torsion,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct ElementSample {
+    pub a: FieldValue,
+    pub exponent: Constant,
+    pub a_cyclotomic_square: FieldValue,
+    pub a_cyclotomic_exp: FieldValue,
+    pub a_cyclotomic_inverse: FieldValue,
+    /// whether a lies in the r-torsion subgroup, i.e. is a pairing value
+    pub a_in_torsion: bool,
+}
+
+/* GT elements do not tell their curve, it comes from the file or the
+ * command line */
+#[derive(Deserialize, Serialize, Debug)]
+pub struct TestSample {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub curve: Option<String>,
+    pub elements: Vec<ElementSample>,
+}
+
+/// Whether `a` lies in the cyclotomic subgroup of order Phi_k(p), k being
+/// the degree of GT over Fp: the embedding degree 4, 6 or 12 of the
+/// supported curves.
+pub fn in_cyclotomic_subgroup<F: Field>(a: &F) -> bool {
+    let frobenius = |i| a.frobenius_map(i);
+    !a.is_zero() && match F::extension_degree() {
+        /* Phi_4(p) = p^2 + 1 */
+        4 => frobenius(2) * a == F::one(),
+        /* Phi_6(p) = p^2 - p + 1 */
+        6 => frobenius(2) * a == frobenius(1),
+        /* Phi_12(p) = p^4 - p^2 + 1 */
+        12 => frobenius(4) * a == frobenius(2),
+        k => panic!("no cyclotomic subgroup for GT of degree {k}"),
+    }
+}
+
+/// Maps a nonzero `f` into the cyclotomic subgroup by raising it to
+/// (p^k - 1)/Phi_k(p), the easy part of the final exponentiation.
+pub fn to_cyclotomic_subgroup<F: Field>(f: F) -> F {
+    let k = F::extension_degree() as usize;
+    let u = f.frobenius_map(k / 2) * f.inverse().expect("f is nonzero");
+    match k {
+        /* p^2 - 1 */
+        4 => u,
+        /* (p^3 - 1)(p + 1) */
+        6 => u.frobenius_map(1) * u,
+        /* (p^6 - 1)(p^2 + 1) */
+        12 => u.frobenius_map(2) * u,
+        k => panic!("no cyclotomic subgroup for GT of degree {k}"),
+    }
+}
+
+fn test_element<P: TestingCurve>(i: usize, x: &ElementSample, suites: &[Suite], report: &mut Report) -> Result<()> {
+    let enabled = |s: Suite| suites.is_empty() || suites.contains(&s);
+    let value = |x: &FieldValue, name: &str| P::TargetField::from_value(x, &format!("$.elements[{i}].{name}"));
+
+    let a = value(&x.a, "a")?;
+    let e = x.exponent.to_biguint(&format!("$.elements[{i}].exponent"))?.to_u64_digits();
+
+    /* the cyclotomic formulas only hold inside the subgroup, elsewhere the
+     * results depend on how the formula is written */
+    if !in_cyclotomic_subgroup(&a) {
+        report.record(format!("elements[{i}] a"), Outcome::Invalid {
+            message: format!("$.elements[{i}].a is not in the cyclotomic subgroup"),
+        });
+    } else {
+        let gt = P::gt_constants();
+        if enabled(Suite::Square) {
+            check_field_eq(report, format!("elements[{i}] cyclotomic_square"),
+                a.cyclotomic_square(), value(&x.a_cyclotomic_square, "a_cyclotomic_square")?, &gt);
+        }
+        if enabled(Suite::Exp) {
+            check_field_eq(report, format!("elements[{i}] cyclotomic_exp"),
+                a.cyclotomic_exp(&e), value(&x.a_cyclotomic_exp, "a_cyclotomic_exp")?, &gt);
+        }
+        if enabled(Suite::Inverse) {
+            let expected = a.cyclotomic_inverse().expect("a is nonzero");
+            check_field_eq(report, format!("elements[{i}] cyclotomic_inverse"),
+                expected, value(&x.a_cyclotomic_inverse, "a_cyclotomic_inverse")?, &gt);
+        }
+    }
+    if enabled(Suite::Torsion) {
+        let in_torsion = a.pow(P::ScalarField::MODULUS).is_one();
+        report.check_eq(format!("elements[{i}] in_torsion"), in_torsion, x.a_in_torsion);
+    }
+    Ok(())
+}
+
+/// Runs the cyclotomic checks over the GT elements of a sample for `P`.
+pub fn test_sample<P: TestingCurve>(sample: &TestSample, suites: &[Suite], report: &mut Report) -> Result<()> {
+    for (i, x) in sample.elements.iter().enumerate() {
+        test_element::<P>(i, x, suites, report)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,146 @@
+use ark_ff::{Field, One, PrimeField, QuadExtConfig, QuadExtField, Zero};
+
+use num_bigint::BigUint;
+
+use crate::report::{Outcome, Report};
+use crate::tower::{FieldValue, ToFieldValue};
+
+/* differing coefficients are listed with their values up to this many */
+const MAX_VALUES: usize = 4;
+
+/* prime field factors up to this size, or their negations, are printed as
+ * small integers */
+const SMALL: u64 = 1 << 32;
+
+/// Prime field coefficients of a value, named by their path down the tower,
+/// such as c1.c0.c2 for an Fq12 element.
+fn coefficients(x: &FieldValue, path: &str, out: &mut Vec<(String, String)>) {
+    match x {
+        FieldValue::Scalar(s, _) => out.push((path.to_string(), s.clone())),
+        FieldValue::List(l) => for (i, c) in l.iter().enumerate() {
+            let path = match path {
+                "" => format!("c{i}"),
+                _ => format!("{path}.c{i}"),
+            };
+            coefficients(c, &path, out);
+        },
+    }
+}
+
+/// The prime field element `x` as a signed integer when it or its
+/// negation is small.
+fn small<F: PrimeField>(x: F) -> Option<String> {
+    let (x, minus_x): (BigUint, BigUint) = (x.into(), (-x).into());
+    if x < BigUint::from(SMALL) {
+        Some(x.to_string())
+    } else if minus_x < BigUint::from(SMALL) {
+        Some(format!("-{minus_x}"))
+    } else {
+        None
+    }
+}
+
+/// Relations `actual` has with `expected` that tower-convention bugs
+/// produce: negation, conjugation and other Frobenius powers, inversion,
+/// and a factor that is a small prime field constant or one of the
+/// `constants`, or their inverse.
+fn relations<F: Field>(expected: F, actual: F, constants: &[(String, F)]) -> Vec<String> {
+    let mut found = vec![];
+    if expected.is_zero() || actual.is_zero() {
+        return found;
+    }
+    let k = F::extension_degree() as usize;
+
+    if actual == -expected {
+        found.push("actual = -expected".to_string());
+    }
+    for i in 1..k {
+        let frobenius = expected.frobenius_map(i);
+        if actual == frobenius && 2 * i == k {
+            found.push("actual = conjugate of expected".to_string());
+        } else if actual == frobenius {
+            found.push(format!("actual = expected^(p^{i})"));
+        } else if actual == -frobenius {
+            found.push(format!("actual = -expected^(p^{i})"));
+        }
+    }
+    let inverse = expected.inverse().expect("expected is nonzero");
+    if actual == inverse {
+        found.push("actual = 1/expected".to_string());
+    }
+    if k.is_multiple_of(2) && actual == inverse.frobenius_map(k / 2) {
+        found.push("actual = 1/conjugate of expected".to_string());
+    }
+
+    let factor = actual * inverse;
+    let mut prime = factor.to_base_prime_field_elements();
+    let c0 = prime.next().expect("a field has coefficients");
+    /* a factor of -1 is the negation found above */
+    if prime.all(|c| c.is_zero()) && factor != -F::one() {
+        match small(c0) {
+            Some(c) => found.push(format!("actual = expected * {c}")),
+            None => found.push(format!("actual = expected * {c0}, a prime field factor")),
+        }
+    }
+    for (name, c) in constants {
+        if factor == *c {
+            found.push(format!("actual = expected * {name}"));
+        } else if factor * c == F::one() {
+            found.push(format!("actual = expected / {name}"));
+        }
+    }
+    found
+}
+
+/// The powers w, w^2, ... of the generator w of a quadratic extension
+/// F[w]/(w^2 - v) below the degree over the prime field: the nonresidues
+/// of the tower, such as v and xi = w^6 for Fq12, and their products.
+pub fn tower_powers<Q: QuadExtConfig>() -> Vec<(String, QuadExtField<Q>)> {
+    let w = QuadExtField::<Q>::new(Zero::zero(), One::one());
+    let k = QuadExtField::<Q>::extension_degree();
+    (1..k).scan(QuadExtField::<Q>::one(), |x, i| {
+        *x *= w;
+        Some((format!("w^{i}"), *x))
+    }).collect()
+}
+
+/// How `actual` differs from `expected`: which prime field coefficients
+/// disagree, and any simple relation between the two.
+pub fn diff<F: Field + ToFieldValue>(expected: F, actual: F, constants: &[(String, F)]) -> Vec<String> {
+    let (mut e, mut a) = (vec![], vec![]);
+    coefficients(&expected.to_value(), "", &mut e);
+    coefficients(&actual.to_value(), "", &mut a);
+    let differing: Vec<_> = e.iter().zip(&a).filter(|(e, a)| e.1 != a.1).collect();
+
+    let mut lines = vec![];
+    if e.len() > 1 {
+        let names: Vec<&str> = differing.iter().map(|(e, _)| e.0.as_str()).collect();
+        lines.push(format!("{} of {} coefficients differ: {}", differing.len(), e.len(), names.join(", ")));
+        if differing.len() <= MAX_VALUES {
+            for ((name, e), (_, a)) in &differing {
+                lines.push(format!("  {name}: expected {e}, actual {a}"));
+            }
+        }
+    }
+    lines.extend(relations(expected, actual, constants));
+    lines
+}
+
+/// Records whether the vector's `actual` field element equals arkworks'
+/// `expected` one, with a [`diff`] of the two when they do not.
+pub fn check_field_eq<F>(report: &mut Report, name: impl Into<String>, expected: F, actual: F, constants: &[(String, F)]) -> bool
+where
+    F: Field + ToFieldValue,
+{
+    let ok = expected == actual;
+    report.record(name, if ok {
+        Outcome::Passed
+    } else {
+        Outcome::Mismatch {
+            expected: expected.to_string(),
+            actual: actual.to_string(),
+            diff: diff(expected, actual, constants),
+        }
+    });
+    ok
+}
@@ -0,0 +1,186 @@
+use core::fmt::Display;
+
+use clap::ValueEnum;
+use serde::{Deserialize, Deserializer, Serialize};
+
+use anyhow::Result;
+
+use crate::diff::{check_field_eq, diff};
+use crate::report::{Outcome, Report};
+use crate::tower::{Constant, FieldValue, FromFieldValue, ToFieldValue};
+
+/// Groups of checks run over a field-arithmetic vector.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Suite {
+    /// a+b, a-b
+    Add,
+    /// a*b, a^2
+    Mul,
+    /// 1/a
+    Inverse,
+    /// sqrt(a), either root
+    Sqrt,
+    /// a^(p^i)
+    Frobenius,
+    /// a^e
+    Pow,
+}
+
+/// Field a field-arithmetic vector holds elements of. Fq3 exists on MNT6
+/// only, Fq4 on MNT4 only, and Fq12 on the Bn and Bls12 curves.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Field {
+    Fr,
+    Fq,
+    Fq2,
+    Fq3,
+    Fq4,
+    Fq6,
+    Fq12,
+}
+
+impl Field {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Field::Fr => "fr",
+            Field::Fq => "fq",
+            Field::Fq2 => "fq2",
+            Field::Fq3 => "fq3",
+            Field::Fq4 => "fq4",
+            Field::Fq6 => "fq6",
+            Field::Fq12 => "fq12",
+        }
+    }
+}
+
+/* tells a null value, kept as Some(None), from an absent one */
+fn nullable<'de, D: Deserializer<'de>>(d: D) -> Result<Option<Option<FieldValue>>, D::Error> {
+    Option::deserialize(d).map(Some)
+}
+
+/* curve and field cannot be detected from the values, they come from the
+ * file or the command line */
+#[derive(Deserialize, Serialize, Debug)]
+pub struct TestSample {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub curve: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub field: Option<Field>,
+    pub a: FieldValue,
+    pub b: FieldValue,
+    pub a_plus_b: FieldValue,
+    pub a_minus_b: FieldValue,
+    pub a_mul_b: FieldValue,
+    pub a_square: FieldValue,
+    /* null when a is zero */
+    pub a_inverse: Option<FieldValue>,
+    /* null when a is not a square, not checked when absent */
+    #[serde(default, deserialize_with = "nullable", skip_serializing_if = "Option::is_none")]
+    pub a_sqrt: Option<Option<FieldValue>>,
+    /* a^(p^i) for i = 1, 2, ... */
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub a_frobenius: Vec<FieldValue>,
+    pub exponent: Constant,
+    pub a_pow_e: FieldValue,
+}
+
+struct TestData<F> {
+    a: F,
+    b: F,
+    a_plus_b: F,
+    a_minus_b: F,
+    a_mul_b: F,
+    a_square: F,
+    a_inverse: Option<F>,
+    a_sqrt: Option<Option<F>>,
+    a_frobenius: Vec<F>,
+    exponent: Vec<u64>,
+    a_pow_e: F,
+}
+
+impl<F: FromFieldValue> TestData<F> {
+    fn from(sample: &TestSample) -> Result<Self> {
+        let value = |x: &FieldValue, path: &str| F::from_value(x, path);
+        let optional = |x: &Option<FieldValue>, path: &str| x.as_ref().map(|x| value(x, path)).transpose();
+        Ok(TestData {
+            a         : value(&sample.a, "$.a")?,
+            b         : value(&sample.b, "$.b")?,
+            a_plus_b  : value(&sample.a_plus_b, "$.a_plus_b")?,
+            a_minus_b : value(&sample.a_minus_b, "$.a_minus_b")?,
+            a_mul_b   : value(&sample.a_mul_b, "$.a_mul_b")?,
+            a_square  : value(&sample.a_square, "$.a_square")?,
+            a_inverse : optional(&sample.a_inverse, "$.a_inverse")?,
+            a_sqrt    : sample.a_sqrt.as_ref().map(|x| optional(x, "$.a_sqrt")).transpose()?,
+            a_frobenius: sample.a_frobenius.iter().enumerate()
+                .map(|(i, x)| value(x, &format!("$.a_frobenius[{i}]")))
+                .collect::<Result<_>>()?,
+            exponent  : sample.exponent.to_biguint("$.exponent")?.to_u64_digits(),
+            a_pow_e   : value(&sample.a_pow_e, "$.a_pow_e")?,
+        })
+    }
+}
+
+fn show<F: Display>(x: &Option<F>) -> String {
+    x.as_ref().map_or("none".to_string(), F::to_string)
+}
+
+fn run_test_case<F: ark_ff::Field + ToFieldValue>(data: &TestData<F>, suites: &[Suite], has_sqrt: bool, report: &mut Report) {
+    let enabled = |s: Suite| suites.is_empty() || suites.contains(&s);
+
+    if enabled(Suite::Add) {
+        check_field_eq(report, "a_plus_b", data.a + data.b, data.a_plus_b, &[]);
+        check_field_eq(report, "a_minus_b", data.a - data.b, data.a_minus_b, &[]);
+    }
+    if enabled(Suite::Mul) {
+        check_field_eq(report, "a_mul_b", data.a * data.b, data.a_mul_b, &[]);
+        check_field_eq(report, "a_square", data.a.square(), data.a_square, &[]);
+    }
+    if enabled(Suite::Inverse) {
+        match (data.a.inverse(), data.a_inverse) {
+            (Some(e), Some(a)) => { check_field_eq(report, "a_inverse", e, a, &[]); }
+            (e, a) => { report.check_eq("a_inverse", show(&e), show(&a)); }
+        }
+    }
+    if enabled(Suite::Sqrt) {
+        match data.a_sqrt {
+            None => {}
+            /* arkworks leaves square roots out of the Fp6 over Fp2 towers */
+            Some(_) if !has_sqrt => report.note("skipped a_sqrt: arkworks has no square roots in this field"),
+            Some(x) => {
+                let expected = data.a.sqrt();
+                let ok = match (expected, x) {
+                    (Some(r), Some(x)) => x == r || x == -r,
+                    (e, x) => e == x,
+                };
+                report.record("a_sqrt", if ok {
+                    Outcome::Passed
+                } else {
+                    let diff = match (expected, x) {
+                        (Some(r), Some(x)) => diff(r, x, &[]),
+                        _ => vec![],
+                    };
+                    Outcome::Mismatch { expected: show(&expected), actual: show(&x), diff }
+                });
+            }
+        }
+    }
+    if enabled(Suite::Frobenius) {
+        for (i, x) in data.a_frobenius.iter().enumerate() {
+            check_field_eq(report, format!("a_frobenius^{}", i + 1), data.a.frobenius_map(i + 1), *x, &[]);
+        }
+    }
+    if enabled(Suite::Pow) {
+        check_field_eq(report, "a_pow_e", data.a.pow(&data.exponent), data.a_pow_e, &[]);
+    }
+}
+
+/// Runs the field-arithmetic checks over one sample of `F`. Square roots
+/// are checked only where arkworks implements them, as `has_sqrt` says.
+pub fn test_sample<F>(sample: &TestSample, suites: &[Suite], has_sqrt: bool, report: &mut Report) -> Result<()>
+where
+    F: ark_ff::Field + FromFieldValue + ToFieldValue,
+{
+    run_test_case(&TestData::<F>::from(sample)?, suites, has_sqrt, report);
+    Ok(())
+}
@@ -0,0 +1,324 @@
+use ark_ec::{
+    pairing::Pairing,
+    short_weierstrass::{SWCurveConfig, Affine, Projective},
+    CurveGroup, Group as _};
+use ark_ff::{CyclotomicMultSubgroup, Field, One, PrimeField, Zero};
+use ark_serialize::Compress;
+use ark_std::{rand::{rngs::StdRng, Rng, SeedableRng}, UniformRand};
+
+use clap::ValueEnum;
+use num_bigint::BigUint;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use crate::curve_ops::{self, Group};
+use crate::cyclotomic;
+use crate::field_ops;
+use crate::groth16;
+use crate::curves::{Curve, TestingCurve};
+use crate::pairing::{Coordinates, TestData, TestSample, FrSet, G1Set, G2Set, GTSet, point_to_sample};
+use crate::precomp::Layout;
+use crate::serialization::{self, arkworks_bytes, zcash_bytes};
+use crate::tower::{Constant, ToFieldValue, to_hex};
+use crate::validate::{self, Validity};
+
+/// Kinds of vector files `generate` writes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Kind {
+    /// pairing vector (Fr, G1, G2, GT and precomputations)
+    Pairing,
+    /// group-law vector over G1 (constants, point_coordinates)
+    G1Ops,
+    /// group-law vector over G2 (constants, point_coordinates)
+    G2Ops,
+    /// valid, off-curve and wrong-subgroup points of G1 with their verdicts
+    G1Validation,
+    /// valid, off-curve and wrong-subgroup points of G2 with their verdicts
+    G2Validation,
+    /// G1 points with their compressed and uncompressed encodings
+    G1Serialization,
+    /// G2 points with their compressed and uncompressed encodings
+    G2Serialization,
+    /// field-arithmetic vector over the field given with --field
+    FieldOps,
+    /// cyclotomic operations on GT elements
+    Cyclotomic,
+    /// Groth16 verifying key, proof and public inputs
+    Groth16,
+}
+
+/// Random scalars satisfying (a*b - vkx*vky)/vkz = c for both triples.
+fn fr_set<P: Pairing>(rng: &mut impl Rng) -> FrSet<P> {
+    let vkz = loop {
+        let vkz = P::ScalarField::rand(rng);
+        if !vkz.is_zero() {
+            break vkz;
+        }
+    };
+    let (vkx, vky) = (P::ScalarField::rand(rng), P::ScalarField::rand(rng));
+    let (a1, b1) = (P::ScalarField::rand(rng), P::ScalarField::rand(rng));
+    let (a2, b2) = (P::ScalarField::rand(rng), P::ScalarField::rand(rng));
+    let vkz_inv = vkz.inverse().unwrap();
+    FrSet {
+        vkx, vky, vkz,
+        a1, b1, c1: (a1*b1 - vkx*vky)*vkz_inv,
+        a2, b2, c2: (a2*b2 - vkx*vky)*vkz_inv,
+    }
+}
+
+/// Pairing vector of random scalars, with the Miller loop outputs of
+/// arkworks as the non-reduced GT values.
+pub fn pairing_sample<P: TestingCurve>(curve: Curve, rng: &mut impl Rng, layout: Layout, coords: Coordinates) -> TestSample {
+    let fr = fr_set::<P>(rng);
+    let g1 = G1Set::<P> {
+        a1:  P::G1::generator() * fr.a1,
+        c1:  P::G1::generator() * fr.c1,
+        a2:  P::G1::generator() * fr.a2,
+        c2:  P::G1::generator() * fr.c2,
+        vkx: P::G1::generator() * fr.vkx,
+    };
+    let g2 = G2Set::<P> {
+        b1:  P::G2::generator() * fr.b1,
+        b2:  P::G2::generator() * fr.b2,
+        vky: P::G2::generator() * fr.vky,
+        vkz: P::G2::generator() * fr.vkz,
+    };
+
+    let ml_a1b1 = P::miller_loop(g1.a1, g2.b1);
+    let ml_a2b2 = P::miller_loop(g1.a2, g2.b2);
+    let dml = P::multi_miller_loop([g1.a1, g1.a2], [g2.b1, g2.b2]);
+    let a1xb1_red = P::final_exponentiation(ml_a1b1).unwrap().0;
+    let a2xb2_red = P::final_exponentiation(ml_a2b2).unwrap().0;
+    let gt = GTSet::<P> {
+        a1xb1: ml_a1b1.0,
+        a2xb2: ml_a2b2.0,
+        a1xb1_red,
+        a2xb2_red,
+        a1xb1_a2xb2: a1xb1_red * a2xb2_red,
+        vkxa1xb1: a1xb1_red.pow(fr.vkx.into_bigint()),
+        ml_a1b1: ml_a1b1.0,
+        ml_a2b2: ml_a2b2.0,
+        dml_a1b1xa2b2: dml.0,
+    };
+
+    let data = TestData { fr, g1, g2, gt };
+    TestSample {
+        curve: Some(curve.name().to_string()),
+        g1_precomputed_type: vec![P::g1_precomputed(data.g1.a1), P::g1_precomputed(data.g1.a2)],
+        g2_precomputed_type: vec![P::g2_precomputed(data.g2.b1, layout), P::g2_precomputed(data.g2.b2, layout)],
+        ..data.to_sample(coords)
+    }
+}
+
+/// Group-law vector over random points of `C`, laid out as
+/// `curve_operation_test_points`.
+pub fn group_sample<C>(curve: Curve, group: Group, rng: &mut impl Rng, coords: Coordinates) -> curve_ops::TestSample
+where
+    C: SWCurveConfig,
+    C::BaseField: ToFieldValue,
+{
+    /* c1 full width in decimal, c2 pushed past the modulus in hex so the
+     * checker has to reduce it */
+    let (c1, c2) = (C::ScalarField::rand(rng), C::ScalarField::rand(rng));
+    let modulus: BigUint = C::ScalarField::MODULUS.into();
+    let constants = vec![
+        Constant::Wide(c1.into_bigint().to_string()),
+        Constant::Wide(format!("0x{:x}", c2.into_bigint().into() + modulus)),
+    ];
+    let p1 = Projective::<C>::generator() * C::ScalarField::rand(rng);
+    let p2 = Projective::<C>::generator() * C::ScalarField::rand(rng);
+    let points = [
+        p1,
+        p2,
+        p1 + p2,
+        p1 - p2,
+        p1 * c1,
+        p2 * c1 + p2 * c2,
+        p1.double(),
+        p1 + p2.into_affine(),
+        p1.into_affine().into(),
+        p2.into_affine().into(),
+    ];
+    curve_ops::TestSample {
+        curve: Some(curve.name().to_string()),
+        group: Some(group),
+        constants,
+        coordinates: Some(coords),
+        point_coordinates: points.iter().map(|p| point_to_sample(p, coords)).collect(),
+    }
+}
+
+/// A point on `C` outside its prime order subgroup, none when the cofactor
+/// is 1.
+fn non_subgroup_point<C: SWCurveConfig>(rng: &mut impl Rng) -> Option<Projective<C>> {
+    if C::COFACTOR[0] == 1 && C::COFACTOR[1..].iter().all(|c| *c == 0) {
+        return None;
+    }
+    loop {
+        let x = C::BaseField::rand(rng);
+        if let Some(p) = Affine::<C>::get_point_from_x_unchecked(x, rng.gen()) {
+            if !p.is_in_correct_subgroup_assuming_on_curve() {
+                return Some(p.into());
+            }
+        }
+    }
+}
+
+/// Validation vector of `C`: the identity, random points of the subgroup,
+/// the same points moved off the curve, and points of the curve outside
+/// the subgroup.
+pub fn validation_sample<C>(curve: Curve, group: Group, rng: &mut impl Rng, coords: Coordinates) -> validate::TestSample
+where
+    C: SWCurveConfig,
+    C::BaseField: ToFieldValue,
+{
+    let mut points = vec![(Projective::<C>::zero(), Validity::Valid)];
+    for _ in 0..2 {
+        let p = Projective::<C>::generator() * C::ScalarField::rand(rng);
+        points.push((p, Validity::Valid));
+        points.push((Projective::new_unchecked(p.x, p.y + C::BaseField::one(), p.z), Validity::NotOnCurve));
+        if let Some(p) = non_subgroup_point::<C>(rng) {
+            points.push((p, Validity::NotInSubgroup));
+        }
+    }
+    validate::TestSample {
+        curve: Some(curve.name().to_string()),
+        group: Some(group),
+        coordinates: Some(coords),
+        points: points.iter()
+            .map(|(p, v)| validate::PointSample { point: point_to_sample(p, coords), expected: *v })
+            .collect(),
+    }
+}
+
+/// The identity, the generator and random points of `C` with their
+/// encodings, the ZCash ones only when `zcash` says the curve is BLS12-381.
+pub fn serialization_sample<C>(curve: Curve, group: Group, rng: &mut impl Rng, coords: Coordinates) -> serialization::TestSample
+where
+    C: SWCurveConfig,
+    C::BaseField: ToFieldValue,
+{
+    let zcash = curve == Curve::Bls12_381;
+    let mut points = vec![Projective::<C>::zero(), Projective::<C>::generator()];
+    points.extend((0..4).map(|_| Projective::<C>::generator() * C::ScalarField::rand(rng)));
+    let hex = |p, compress| Some(to_hex(&arkworks_bytes(p, compress)));
+    let zcash_hex = |p, compress| zcash.then(|| to_hex(&zcash_bytes(p, compress)));
+    serialization::TestSample {
+        curve: Some(curve.name().to_string()),
+        group: Some(group),
+        coordinates: Some(coords),
+        points: points.iter()
+            .map(|p| serialization::PointSample {
+                point: point_to_sample(p, coords),
+                compressed: hex(p, Compress::Yes),
+                uncompressed: hex(p, Compress::No),
+                zcash_compressed: zcash_hex(p, Compress::Yes),
+                zcash_uncompressed: zcash_hex(p, Compress::No),
+            })
+            .collect(),
+    }
+}
+
+/// Field-arithmetic vector of random elements of `F`, with the square root
+/// left out where arkworks has none.
+pub fn field_sample<F>(rng: &mut impl Rng, has_sqrt: bool) -> field_ops::TestSample
+where
+    F: Field + ToFieldValue,
+{
+    let (a, b) = (F::rand(rng), F::rand(rng));
+    let exponent = BigUint::from_bytes_le(&rng.gen::<[u8; 32]>());
+    field_ops::TestSample {
+        curve: None,
+        field: None,
+        a: a.to_value(),
+        b: b.to_value(),
+        a_plus_b: (a + b).to_value(),
+        a_minus_b: (a - b).to_value(),
+        a_mul_b: (a * b).to_value(),
+        a_square: a.square().to_value(),
+        a_inverse: a.inverse().map(|x| x.to_value()),
+        a_sqrt: has_sqrt.then(|| a.sqrt().map(|x| x.to_value())),
+        a_frobenius: (1..=F::extension_degree() as usize).map(|i| a.frobenius_map(i).to_value()).collect(),
+        exponent: Constant::Wide(format!("0x{exponent:x}")),
+        a_pow_e: a.pow(exponent.to_u64_digits()).to_value(),
+    }
+}
+
+/// Cyclotomic vector of `P`: a pairing value, an element of the cyclotomic
+/// subgroup outside the r-torsion, and one.
+pub fn cyclotomic_sample<P: TestingCurve>(curve: Curve, rng: &mut impl Rng) -> cyclotomic::TestSample {
+    let pairing = P::pairing(P::G1::rand(rng), P::G2::rand(rng)).0;
+    let cyclotomic = cyclotomic::to_cyclotomic_subgroup(P::TargetField::rand(rng));
+    let elements = [pairing, cyclotomic, P::TargetField::one()].into_iter().map(|a| {
+        let exponent = BigUint::from_bytes_le(&rng.gen::<[u8; 32]>());
+        cyclotomic::ElementSample {
+            a: a.to_value(),
+            a_cyclotomic_square: a.cyclotomic_square().to_value(),
+            a_cyclotomic_exp: a.cyclotomic_exp(exponent.to_u64_digits()).to_value(),
+            a_cyclotomic_inverse: a.cyclotomic_inverse().unwrap().to_value(),
+            a_in_torsion: a.pow(P::ScalarField::MODULUS).is_one(),
+            exponent: Constant::Wide(format!("0x{exponent:x}")),
+        }
+    }).collect();
+    cyclotomic::TestSample { curve: Some(curve.name().to_string()), elements }
+}
+
+/// Groth16 vector of `P` with three public inputs. There is no circuit:
+/// the key comes from random trapdoor scalars and C is solved for, the way
+/// the simulator of the zero-knowledge proof does.
+pub fn groth16_sample<P: TestingCurve>(curve: Curve, rng: &mut impl Rng, coords: Coordinates) -> groth16::TestSample {
+    let mut scalar = || loop {
+        let x = P::ScalarField::rand(rng);
+        if !x.is_zero() {
+            break x;
+        }
+    };
+    let (alpha, beta, gamma, delta) = (scalar(), scalar(), scalar(), scalar());
+    let gamma_abc: Vec<_> = (0..4).map(|_| scalar()).collect();
+    let inputs: Vec<_> = (0..3).map(|_| scalar()).collect();
+    let (a, b) = (scalar(), scalar());
+
+    /* a*b = alpha*beta + l*gamma + c*delta */
+    let l = gamma_abc[1..].iter().zip(&inputs).fold(gamma_abc[0], |l, (p, x)| l + *p * x);
+    let c = (a * b - alpha * beta - l * gamma) * delta.inverse().unwrap();
+
+    let (g1, g2) = (P::G1::generator(), P::G2::generator());
+    let g1_point = |x: P::ScalarField| point_to_sample(&(g1 * x), coords);
+    let g2_point = |x: P::ScalarField| point_to_sample(&(g2 * x), coords);
+    groth16::TestSample {
+        curve: Some(curve.name().to_string()),
+        coordinates: Some(coords),
+        vk: groth16::VerifyingKeySample {
+            alpha_g1: g1_point(alpha),
+            beta_g2: g2_point(beta),
+            gamma_g2: g2_point(gamma),
+            delta_g2: g2_point(delta),
+            gamma_abc_g1: gamma_abc.iter().map(|x| g1_point(*x)).collect(),
+        },
+        proof: groth16::ProofSample { a: g1_point(a), b: g2_point(b), c: g1_point(c) },
+        public_inputs: inputs.iter().map(ToFieldValue::to_value).collect(),
+        valid: true,
+    }
+}
+
+/// Generates a sample of `kind` for `P` from `seed`, as a JSON value.
+pub fn generate<P: TestingCurve>(curve: Curve, kind: Kind, seed: u64, layout: Layout, coords: Coordinates, field: field_ops::Field) -> Result<Value> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    Ok(match kind {
+        Kind::Pairing => serde_json::to_value(pairing_sample::<P>(curve, &mut rng, layout, coords))?,
+        Kind::G1Ops => serde_json::to_value(group_sample::<P::G1Config>(curve, Group::G1, &mut rng, coords))?,
+        Kind::G2Ops => serde_json::to_value(group_sample::<P::G2Config>(curve, Group::G2, &mut rng, coords))?,
+        Kind::G1Validation => serde_json::to_value(validation_sample::<P::G1Config>(curve, Group::G1, &mut rng, coords))?,
+        Kind::G2Validation => serde_json::to_value(validation_sample::<P::G2Config>(curve, Group::G2, &mut rng, coords))?,
+        Kind::G1Serialization => serde_json::to_value(serialization_sample::<P::G1Config>(curve, Group::G1, &mut rng, coords))?,
+        Kind::G2Serialization => serde_json::to_value(serialization_sample::<P::G2Config>(curve, Group::G2, &mut rng, coords))?,
+        Kind::Groth16 => serde_json::to_value(groth16_sample::<P>(curve, &mut rng, coords))?,
+        Kind::Cyclotomic => serde_json::to_value(cyclotomic_sample::<P>(curve, &mut rng))?,
+        Kind::FieldOps => serde_json::to_value(&field_ops::TestSample {
+            curve: Some(curve.name().to_string()),
+            field: Some(field),
+            ..P::field_sample(field, &mut rng)?
+        })?,
+    })
+}
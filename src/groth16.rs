@@ -0,0 +1,113 @@
+use ark_ec::pairing::Pairing;
+use ark_ff::Zero;
+
+use serde::{Deserialize, Serialize};
+
+use anyhow::{ensure, Result};
+
+use crate::curves::TestingCurve;
+use crate::pairing::{Coordinates, ProjectivePoint, point_from_sample};
+use crate::report::Report;
+use crate::tower::{FieldValue, FromFieldValue};
+use crate::validate::{check_inputs, validity};
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct VerifyingKeySample {
+    pub alpha_g1: ProjectivePoint,
+    pub beta_g2: ProjectivePoint,
+    pub gamma_g2: ProjectivePoint,
+    pub delta_g2: ProjectivePoint,
+    /// one point per public input, after the constant one
+    pub gamma_abc_g1: Vec<ProjectivePoint>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct ProofSample {
+    pub a: ProjectivePoint,
+    pub b: ProjectivePoint,
+    pub c: ProjectivePoint,
+}
+
+fn verifies() -> bool {
+    true
+}
+
+/* curve is not detected, it comes from the file or the command line */
+#[derive(Deserialize, Serialize, Debug)]
+pub struct TestSample {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub curve: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coordinates: Option<Coordinates>,
+    pub vk: VerifyingKeySample,
+    pub proof: ProofSample,
+    pub public_inputs: Vec<FieldValue>,
+    /// whether the prover expects the proof to verify, true when absent
+    #[serde(default = "verifies")]
+    pub valid: bool,
+}
+
+pub struct VerifyingKey<P: Pairing> {
+    pub alpha: P::G1,
+    pub beta: P::G2,
+    pub gamma: P::G2,
+    pub delta: P::G2,
+    pub gamma_abc: Vec<P::G1>,
+}
+
+pub struct Proof<P: Pairing> {
+    pub a: P::G1,
+    pub b: P::G2,
+    pub c: P::G1,
+}
+
+/// Whether `proof` verifies for `vk` and the public inputs:
+/// e(A, B) = e(alpha, beta) * e(L, gamma) * e(C, delta), where L is the
+/// sum of the gamma_abc points weighted by 1 and the inputs.
+pub fn verify<P: Pairing>(vk: &VerifyingKey<P>, proof: &Proof<P>, inputs: &[P::ScalarField]) -> bool {
+    let l = vk.gamma_abc[1..].iter().zip(inputs)
+        .fold(vk.gamma_abc[0], |l, (p, x)| l + *p * x);
+    P::multi_pairing([proof.a, -vk.alpha, -l, -proof.c], [proof.b, vk.beta, vk.gamma, vk.delta]).is_zero()
+}
+
+/// Checks the arkworks verdict on the proof of a sample for `P` against the
+/// one the sample expects, after checking that its points are valid.
+pub fn test_sample<P: TestingCurve>(sample: &TestSample, report: &mut Report) -> Result<()> {
+    let coords = sample.coordinates.unwrap_or_default();
+    let (x, y) = (&sample.vk, &sample.proof);
+    ensure!(x.gamma_abc_g1.len() == sample.public_inputs.len() + 1,
+        "$.vk.gamma_abc_g1: expected {} points for {} public inputs, got {}",
+        sample.public_inputs.len() + 1, sample.public_inputs.len(), x.gamma_abc_g1.len());
+
+    let g1 = |x: &ProjectivePoint, path: &str| point_from_sample::<P::G1Config>(x, coords, path);
+    let g2 = |x: &ProjectivePoint, path: &str| point_from_sample::<P::G2Config>(x, coords, path);
+    let vk = VerifyingKey::<P> {
+        alpha: g1(&x.alpha_g1, "$.vk.alpha_g1")?,
+        beta : g2(&x.beta_g2, "$.vk.beta_g2")?,
+        gamma: g2(&x.gamma_g2, "$.vk.gamma_g2")?,
+        delta: g2(&x.delta_g2, "$.vk.delta_g2")?,
+        gamma_abc: x.gamma_abc_g1.iter().enumerate()
+            .map(|(i, x)| g1(x, &format!("$.vk.gamma_abc_g1[{i}]")))
+            .collect::<Result<_>>()?,
+    };
+    let proof = Proof::<P> {
+        a: g1(&y.a, "$.proof.a")?,
+        b: g2(&y.b, "$.proof.b")?,
+        c: g1(&y.c, "$.proof.c")?,
+    };
+    let inputs = sample.public_inputs.iter().enumerate()
+        .map(|(i, x)| P::ScalarField::from_value(x, &format!("$.public_inputs[{i}]")))
+        .collect::<Result<Vec<_>>>()?;
+
+    let g1_points = [("$.vk.alpha_g1", vk.alpha), ("$.proof.a", proof.a), ("$.proof.c", proof.c)]
+        .into_iter()
+        .map(|(path, p)| (path.to_string(), validity(&p)))
+        .chain(vk.gamma_abc.iter().enumerate().map(|(i, p)| (format!("$.vk.gamma_abc_g1[{i}]"), validity(p))));
+    let g2_points = [("$.vk.beta_g2", vk.beta), ("$.vk.gamma_g2", vk.gamma), ("$.vk.delta_g2", vk.delta), ("$.proof.b", proof.b)]
+        .into_iter()
+        .map(|(path, p)| (path.to_string(), validity(&p)));
+    check_inputs(report, g1_points.chain(g2_points));
+
+    report.check_eq("groth16 verification", verify(&vk, &proof, &inputs), sample.valid);
+    Ok(())
+}
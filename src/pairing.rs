@@ -0,0 +1,488 @@
+use ark_ec::{
+    pairing::{Pairing, MillerLoopOutput},
+    CurveGroup, Group};
+use ark_ec::short_weierstrass::{SWCurveConfig, Affine, Projective};
+use ark_ff::{PrimeField, Field, Zero};
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+use anyhow::{anyhow, ensure, Result};
+
+use crate::curves::TestingCurve;
+use crate::diff::check_field_eq;
+use crate::report::{Outcome, Report};
+use crate::properties::check_properties;
+use crate::precomp::{G1Precomputed, G2Precomputed, g2_precomputed_point};
+use crate::tower::{FieldValue, FromFieldValue, ToFieldValue};
+use crate::validate::{check_inputs, validity};
+
+/// Groups of checks run over a pairing vector.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Suite {
+    /// Fr relation (a*b - vkx*vky)/vkz = c
+    Scalars,
+    /// G1/G2 points equal generator times their scalars
+    Points,
+    /// pairing products
+    Pairing,
+    /// Miller loop and final exponentiation compared separately
+    MillerLoop,
+    /// G1 precomputation (PX, PY and the MNT twists) against G1Prepared
+    G1Precomp,
+    /// G2 line coefficients (ell_0, ell_VW, ell_VV or MNT Ate coefficients)
+    /// against G2Prepared
+    G2Precomp,
+    /// bilinearity, identity and non-degeneracy over a1, a2, b1, vkx, vky
+    Properties,
+}
+
+/* coordinates are Fq values for G1 and Fq2/Fq3 values for G2 */
+#[derive(Deserialize, Serialize, Debug)]
+pub struct ProjectivePoint(Vec<FieldValue>);
+
+/// Coordinate system the points of a vector file are written in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Coordinates {
+    /// (x, y), with (0, 0) for the point at infinity
+    Affine,
+    /// homogeneous (X, Y, Z) for (X/Z, Y/Z)
+    Projective,
+    /// (X, Y, Z) for (X/Z^2, Y/Z^3), the arkworks representation
+    #[default]
+    Jacobian,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct TestSample {
+    /* optional, detected from the G1 points when absent */
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub curve: Option<String>,
+    /* jacobian when absent */
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coordinates: Option<Coordinates>,
+    #[serde(rename="Fr")]
+    pub fr:Vec<FieldValue>,
+    #[serde(rename="G1")]
+    pub g1:Vec<ProjectivePoint>,
+    #[serde(rename="G2")]
+    pub g2:Vec<ProjectivePoint>,
+    #[serde(rename="GT")]
+    pub gt:Vec<FieldValue>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub g1_precomputed_type: Vec<G1Precomputed>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub g2_precomputed_type: Vec<G2Precomputed>,
+}
+
+/// Reads a point written in `coords`, converting it to the Jacobian
+/// coordinates of arkworks.
+pub fn point_from_sample<C>(x: &ProjectivePoint, coords: Coordinates, path: &str) -> Result<Projective<C>>
+where
+    C: SWCurveConfig,
+    C::BaseField: FromFieldValue,
+{
+    let coordinate = |i: usize| C::BaseField::from_value(&x.0[i], &format!("{path}[{i}]"));
+    match coords {
+        Coordinates::Affine => {
+            ensure!(x.0.len() == 2, "{path}: expected 2 affine coordinates, got {}", x.0.len());
+            let (x, y) = (coordinate(0)?, coordinate(1)?);
+            Ok(if x.is_zero() && y.is_zero() {
+                Projective::zero()
+            } else {
+                Affine::new_unchecked(x, y).into()
+            })
+        }
+        Coordinates::Projective | Coordinates::Jacobian => {
+            ensure!(x.0.len() == 3, "{path}: expected 3 projective coordinates, got {}", x.0.len());
+            let (x, y, z) = (coordinate(0)?, coordinate(1)?, coordinate(2)?);
+            Ok(match coords {
+                /* (X/Z, Y/Z) = (XZ/Z^2, YZ^2/Z^3) */
+                Coordinates::Projective => Projective::new_unchecked(x * z, y * z.square(), z),
+                _ => Projective::new_unchecked(x, y, z),
+            })
+        }
+    }
+}
+
+/// Writes a point in `coords`. Projective points are not normalized, the
+/// Jacobian Z is carried over as Z^3.
+pub fn point_to_sample<C>(p: &Projective<C>, coords: Coordinates) -> ProjectivePoint
+where
+    C: SWCurveConfig,
+    C::BaseField: ToFieldValue,
+{
+    let coordinates = match coords {
+        Coordinates::Affine => {
+            let p = p.into_affine();
+            match p.infinity {
+                true => vec![C::BaseField::zero(); 2],
+                false => vec![p.x, p.y],
+            }
+        }
+        /* (X/Z^2, Y/Z^3) = (XZ/Z^3, Y/Z^3) */
+        Coordinates::Projective => vec![p.x * p.z, p.y, p.z * p.z.square()],
+        Coordinates::Jacobian => vec![p.x, p.y, p.z],
+    };
+    ProjectivePoint(coordinates.iter().map(ToFieldValue::to_value).collect())
+}
+
+#[derive(Debug)]
+pub struct FrSet<P: Pairing> {
+    pub vkx: P::ScalarField,
+    pub vky: P::ScalarField,
+    pub vkz: P::ScalarField,
+    pub a1: P::ScalarField,
+    pub b1: P::ScalarField,
+    pub c1: P::ScalarField,
+    pub a2: P::ScalarField,
+    pub b2: P::ScalarField,
+    pub c2: P::ScalarField,
+}
+
+#[derive(Debug)]
+pub struct G1Set<P: Pairing> {
+    pub a1:  P::G1,
+    pub c1:  P::G1,
+    pub a2:  P::G1,
+    pub c2:  P::G1,
+    pub vkx: P::G1,
+}
+
+#[derive(Debug)]
+pub struct G2Set<P: Pairing> {
+    pub b1:  P::G2,
+    pub b2:  P::G2,
+    pub vky: P::G2,
+    pub vkz: P::G2,
+}
+
+/* non-reduced values (a1xb1, a2xb2, ml_*, dml_*) are the external Miller loop
+ * outputs, which only agree with arkworks after final exponentiation */
+#[derive(Debug)]
+pub struct GTSet<P: Pairing> {
+    pub a1xb1:        P::TargetField,
+    pub a2xb2:        P::TargetField,
+    pub a1xb1_red:    P::TargetField,
+    pub a2xb2_red:    P::TargetField,
+    pub a1xb1_a2xb2:  P::TargetField,
+    pub vkxa1xb1:     P::TargetField,
+    pub ml_a1b1:      P::TargetField,
+    pub ml_a2b2:      P::TargetField,
+    pub dml_a1b1xa2b2:P::TargetField,
+}
+
+pub struct TestData<P:Pairing> {
+    pub fr: FrSet<P>,
+    pub g1: G1Set<P>,
+    pub g2: G2Set<P>,
+    pub gt: GTSet<P>,
+}
+
+/// Entry `i` of the list at `path`, which should hold `count` of them.
+fn entry<'a, T>(list: &'a [T], i: usize, count: usize, path: &str) -> Result<&'a T> {
+    list.get(i).ok_or_else(|| anyhow!("{path}: expected {count} entries, got {}", list.len()))
+}
+
+impl<P: TestingCurve> TestData<P> {
+    pub fn from(sample: &TestSample) -> Result<Self> {
+        let fr = |i: usize| P::ScalarField::from_value(entry(&sample.fr, i, 9, "$.Fr")?, &format!("$.Fr[{i}]"));
+        let coords = sample.coordinates.unwrap_or_default();
+        let g1 = |i: usize| point_from_sample(entry(&sample.g1, i, 5, "$.G1")?, coords, &format!("$.G1[{i}]"));
+        let g2 = |i: usize| point_from_sample(entry(&sample.g2, i, 4, "$.G2")?, coords, &format!("$.G2[{i}]"));
+        let gt = |i: usize| P::TargetField::from_value(entry(&sample.gt, i, 9, "$.GT")?, &format!("$.GT[{i}]"));
+        Ok(Self{
+            fr : FrSet {
+                vkx : fr(0)?,
+                vky : fr(1)?,
+                vkz : fr(2)?,
+                a1  : fr(3)?,
+                b1  : fr(4)?,
+                c1  : fr(5)?,
+                a2  : fr(6)?,
+                b2  : fr(7)?,
+                c2  : fr(8)?,
+            },
+            g1 : G1Set {
+                a1:  g1(0)?,
+                c1:  g1(1)?,
+                a2:  g1(2)?,
+                c2:  g1(3)?,
+                vkx: g1(4)?,
+            },
+            g2 : G2Set {
+                b1:  g2(0)?,
+                b2:  g2(1)?,
+                vky: g2(2)?,
+                vkz: g2(3)?,
+            },
+            gt : GTSet {
+                a1xb1:         gt(0)?,
+                a2xb2:         gt(1)?,
+                a1xb1_red:     gt(2)?,
+                a2xb2_red:     gt(3)?,
+                a1xb1_a2xb2:   gt(4)?,
+                vkxa1xb1:      gt(5)?,
+                ml_a1b1:       gt(6)?,
+                ml_a2b2:       gt(7)?,
+                dml_a1b1xa2b2: gt(8)?,
+            }
+        })
+    }
+
+    /// The sample `from` reads this data back from, without precomputations,
+    /// with its points written in `coords`.
+    pub fn to_sample(&self, coords: Coordinates) -> TestSample {
+        let (fr, g1, g2, gt) = (&self.fr, &self.g1, &self.g2, &self.gt);
+        let point = |p: &P::G1| point_to_sample(p, coords);
+        let point2 = |p: &P::G2| point_to_sample(p, coords);
+        TestSample {
+            curve: None,
+            coordinates: Some(coords),
+            fr: [fr.vkx, fr.vky, fr.vkz, fr.a1, fr.b1, fr.c1, fr.a2, fr.b2, fr.c2]
+                .iter().map(ToFieldValue::to_value).collect(),
+            g1: [g1.a1, g1.c1, g1.a2, g1.c2, g1.vkx].iter().map(point).collect(),
+            g2: [g2.b1, g2.b2, g2.vky, g2.vkz].iter().map(point2).collect(),
+            gt: [gt.a1xb1, gt.a2xb2, gt.a1xb1_red, gt.a2xb2_red, gt.a1xb1_a2xb2, gt.vkxa1xb1,
+                 gt.ml_a1b1, gt.ml_a2b2, gt.dml_a1b1xa2b2]
+                .iter().map(ToFieldValue::to_value).collect(),
+            g1_precomputed_type: vec![],
+            g2_precomputed_type: vec![],
+        }
+    }
+}
+
+pub fn test_dataset<P: TestingCurve>(t: &TestData<P>, suites: &[Suite], report: &mut Report) {
+    let enabled = |s: Suite| suites.is_empty() || suites.contains(&s);
+
+    /* consistency check */
+    if enabled(Suite::Scalars) {
+        match t.fr.vkz.inverse() {
+            Some(vkz_inv) => {
+                report.check_eq("scalars c1", (t.fr.a1*t.fr.b1-t.fr.vkx*t.fr.vky)*vkz_inv, t.fr.c1);
+                report.check_eq("scalars c2", (t.fr.a2*t.fr.b2-t.fr.vkx*t.fr.vky)*vkz_inv, t.fr.c2);
+            }
+            None => report.record("scalars vkz", Outcome::Failed { message: "vkz is not invertible".into() }),
+        }
+    }
+
+    /* checking points correspond to scalars */
+    if enabled(Suite::Points) {
+        let a1 = P::G1::generator() * t.fr.a1;
+        let a2 = P::G1::generator() * t.fr.a2;
+        let b1 = P::G2::generator() * t.fr.b1;
+        let b2 = P::G2::generator() * t.fr.b2;
+        let c1 = P::G1::generator() * t.fr.c1;
+        let c2 = P::G1::generator() * t.fr.c2;
+        let vkx = P::G1::generator() * t.fr.vkx;
+        let vky = P::G2::generator() * t.fr.vky;
+        let vkz = P::G2::generator() * t.fr.vkz;
+        report.check_eq("points a1",  a1,  t.g1.a1);
+        report.check_eq("points a2",  a2,  t.g1.a2);
+        report.check_eq("points c1",  c1,  t.g1.c1);
+        report.check_eq("points c2",  c2,  t.g1.c2);
+        report.check_eq("points b1",  b1,  t.g2.b1);
+        report.check_eq("points b2",  b2,  t.g2.b2);
+        report.check_eq("points vkx", vkx, t.g1.vkx);
+        report.check_eq("points vky", vky, t.g2.vky);
+        report.check_eq("points vkz", vkz, t.g2.vkz);
+    }
+
+    if enabled(Suite::MillerLoop) {
+        test_stages(t, report);
+    }
+
+    if !enabled(Suite::Pairing) {
+        return;
+    }
+
+    /* pairings are taken over the vector's own points so that a wrong
+     * input point is reported by the points suite only; reduce gives zero
+     * for a Miller loop output that is not invertible */
+    let reduce = |f: P::TargetField| P::final_exponentiation(MillerLoopOutput(f))
+        .map_or_else(P::TargetField::zero, |e| e.0);
+    let gt = P::gt_constants();
+
+    let a1b1 = P::pairing(t.g1.a1, t.g2.b1);
+    check_field_eq(report, "pairing a1b1", a1b1.0, t.gt.a1xb1_red, &gt);
+    check_field_eq(report, "pairing a1b1 non-reduced", a1b1.0, reduce(t.gt.a1xb1), &gt);
+
+    let a2b2 = P::pairing(t.g1.a2, t.g2.b2);
+    check_field_eq(report, "pairing a2b2", a2b2.0, t.gt.a2xb2_red, &gt);
+    check_field_eq(report, "pairing a2b2 non-reduced", a2b2.0, reduce(t.gt.a2xb2), &gt);
+
+    check_field_eq(report, "pairing product e(a1,b1) * e(a2,b2)", a1b1.0 * a2b2.0, t.gt.a1xb1_a2xb2, &gt);
+    let product = P::multi_pairing([t.g1.a1, t.g1.a2], [t.g2.b1, t.g2.b2]);
+    check_field_eq(report, "pairing multi_pairing a1b1 a2b2", product.0, t.gt.a1xb1_a2xb2, &gt);
+
+    check_field_eq(report, "pairing power e(a1,b1)^vkx", a1b1.0.pow(t.fr.vkx.into_bigint()), t.gt.vkxa1xb1, &gt);
+
+    /* the Miller loop entries are compared by the miller-loop suite */
+    let p1 = P::pairing(t.g1.vkx, t.g2.vky).0 * P::pairing(t.g1.c1, t.g2.vkz).0;
+    check_field_eq(report, "pairing e(a1,b1) vs e(vkx,vky) * e(c1,vkz)", p1, a1b1.0, &gt);
+}
+
+/// Compares one pairing with the vector stage by stage: the raw Miller
+/// loop, arkworks' final exponentiation applied to the vector's Miller loop,
+/// and the full pairing.
+fn pairing_stages<P: TestingCurve>(
+    label: &str,
+    ml: MillerLoopOutput<P>,
+    vector_ml: P::TargetField,
+    vector_reduced: P::TargetField,
+    gt: &[(String, P::TargetField)],
+    report: &mut Report,
+) {
+    let reduce = |f: P::TargetField| P::final_exponentiation(MillerLoopOutput(f))
+        .map_or_else(P::TargetField::zero, |e| e.0);
+
+    check_field_eq(report, format!("stages {label} miller loop"), ml.0, vector_ml, gt);
+    check_field_eq(report, format!("stages {label} final exponentiation"), vector_reduced, reduce(vector_ml), gt);
+    check_field_eq(report, format!("stages {label} pairing"), reduce(ml.0), vector_reduced, gt);
+}
+
+fn test_stages<P: TestingCurve>(t: &TestData<P>, report: &mut Report) {
+    let gt = P::gt_constants();
+    let checks = [
+        ("a1b1", P::miller_loop(t.g1.a1, t.g2.b1), t.gt.ml_a1b1, t.gt.a1xb1_red),
+        ("a2b2", P::miller_loop(t.g1.a2, t.g2.b2), t.gt.ml_a2b2, t.gt.a2xb2_red),
+        ("a1b1 a2b2",
+            P::multi_miller_loop([t.g1.a1, t.g1.a2], [t.g2.b1, t.g2.b2]),
+            t.gt.dml_a1b1xa2b2, t.gt.a1xb1_a2xb2),
+    ];
+    for (label, ml, vector_ml, vector_reduced) in checks {
+        pairing_stages(label, ml, vector_ml, vector_reduced, &gt, report);
+    }
+}
+
+/// Checks the precomputed G1 entries, made for a1 and a2, against arkworks,
+/// reporting missing and extra entries as [`test_g2_precomputed`] does.
+fn test_g1_precomputed<P: TestingCurve>(t: &TestData<P>, precomputed: &[G1Precomputed], required: bool, report: &mut Report) {
+    if precomputed.is_empty() && !required {
+        report.note("skipped g1 precomputation: the vector has no g1_precomputed_type");
+        return;
+    }
+    let points = [("a1", t.g1.a1), ("a2", t.g1.a2)];
+    for (i, (label, p)) in points.into_iter().enumerate() {
+        let path = format!("$.g1_precomputed_type[{i}]");
+        let missing = || anyhow!("{path}: missing, expected {} entries, got {}", points.len(), precomputed.len());
+        let result = precomputed.get(i).ok_or_else(missing).and_then(|x| P::check_g1_precomputed(label, &path, x, p));
+        report.check(format!("g1 precomputation {label}"), result);
+    }
+    for i in points.len()..precomputed.len() {
+        report.record(format!("g1 precomputation [{i}]"), Outcome::Failed {
+            message: format!("$.g1_precomputed_type[{i}]: unexpected entry, only a1 and a2 are precomputed"),
+        });
+    }
+}
+
+/// Checks the precomputed G2 entries, made for b1 and b2, against arkworks.
+/// Missing entries fail, unless there are none and the suite was not asked
+/// for by name, and entries past b2 fail too.
+fn test_g2_precomputed<P: TestingCurve>(t: &TestData<P>, precomputed: &[G2Precomputed], required: bool, report: &mut Report) {
+    if precomputed.is_empty() && !required {
+        report.note("skipped g2 precomputation: the vector has no g2_precomputed_type");
+        return;
+    }
+    let points = [("b1", t.g2.b1), ("b2", t.g2.b2)];
+    for (i, (label, q)) in points.into_iter().enumerate() {
+        let path = format!("$.g2_precomputed_type[{i}]");
+        let missing = || anyhow!("{path}: missing, expected {} entries, got {}", points.len(), precomputed.len());
+        let result = precomputed.get(i).ok_or_else(missing).and_then(|x| {
+            let point = g2_precomputed_point::<P::G2Config>(&path, x)?.into();
+            ensure!(point == q, "{label}: precomputed coordinates are not the vector's point");
+            P::check_g2_precomputed(label, &path, x, point)
+        });
+        report.check(format!("g2 precomputation {label}"), result);
+    }
+    for i in points.len()..precomputed.len() {
+        report.record(format!("g2 precomputation [{i}]"), Outcome::Failed {
+            message: format!("$.g2_precomputed_type[{i}]: unexpected entry, only b1 and b2 are precomputed"),
+        });
+    }
+}
+
+/// Loads a pairing sample for `P` and runs the selected suites on it.
+pub fn test_sample<P: TestingCurve>(sample: &TestSample, suites: &[Suite], report: &mut Report) -> Result<()> {
+    let enabled = |s: Suite| suites.is_empty() || suites.contains(&s);
+
+    let data = TestData::<P>::from(sample)?;
+    let (g1, g2) = (&data.g1, &data.g2);
+    let g1 = [g1.a1, g1.c1, g1.a2, g1.c2, g1.vkx].into_iter().enumerate()
+        .map(|(i, p)| (format!("$.G1[{i}]"), validity(&p)));
+    let g2 = [g2.b1, g2.b2, g2.vky, g2.vkz].into_iter().enumerate()
+        .map(|(i, p)| (format!("$.G2[{i}]"), validity(&p)));
+    check_inputs(report, g1.chain(g2));
+
+    /* the suites are independent, they run in parallel and their cases are
+     * reported in this order */
+    let order = [Suite::Scalars, Suite::Points, Suite::MillerLoop, Suite::Pairing,
+                 Suite::G1Precomp, Suite::G2Precomp, Suite::Properties];
+    let selected: Vec<Suite> = order.into_iter().filter(|s| enabled(*s)).collect();
+    report.run_parallel(&selected, |suite, report| match suite {
+        Suite::G1Precomp => test_g1_precomputed(&data, &sample.g1_precomputed_type, suites.contains(suite), report),
+        Suite::G2Precomp => test_g2_precomputed(&data, &sample.g2_precomputed_type, suites.contains(suite), report),
+        Suite::Properties => check_properties::<P>("properties",
+            data.g1.a1, data.g1.a2, data.g2.b1, data.fr.vkx, data.fr.vky, report),
+        suite => test_dataset(&data, &[*suite], report),
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_ec::CurveGroup;
+    use ark_std::{rand::{rngs::StdRng, SeedableRng}, UniformRand};
+
+    /* the random points have Z != 1, so the conversions are exercised */
+    fn round_trip<C>(coords: Coordinates)
+    where
+        C: SWCurveConfig,
+        C::BaseField: FromFieldValue + ToFieldValue,
+    {
+        let mut rng = StdRng::seed_from_u64(0);
+        let p = Projective::<C>::rand(&mut rng);
+        for p in [p, p.double(), Projective::zero()] {
+            let q: Projective<C> = point_from_sample(&point_to_sample(&p, coords), coords, "$").unwrap();
+            assert_eq!(q, p, "{coords:?}");
+        }
+    }
+
+    #[test]
+    fn affine_round_trip() {
+        round_trip::<ark_bn254::g1::Config>(Coordinates::Affine);
+        round_trip::<ark_bls12_381::g2::Config>(Coordinates::Affine);
+        round_trip::<ark_mnt6_298::g2::Config>(Coordinates::Affine);
+    }
+
+    #[test]
+    fn projective_round_trip() {
+        round_trip::<ark_bn254::g1::Config>(Coordinates::Projective);
+        round_trip::<ark_bls12_381::g2::Config>(Coordinates::Projective);
+        round_trip::<ark_mnt6_298::g2::Config>(Coordinates::Projective);
+    }
+
+    #[test]
+    fn jacobian_round_trip() {
+        round_trip::<ark_bn254::g1::Config>(Coordinates::Jacobian);
+        round_trip::<ark_bls12_381::g2::Config>(Coordinates::Jacobian);
+        round_trip::<ark_mnt6_298::g2::Config>(Coordinates::Jacobian);
+    }
+
+    /* a round trip would not catch both directions using the same wrong
+     * formula, so the homogeneous form is also checked against X/Z, Y/Z */
+    #[test]
+    fn projective_is_homogeneous() {
+        type C = ark_bn254::g1::Config;
+        let mut rng = StdRng::seed_from_u64(0);
+        let p = Projective::<C>::rand(&mut rng).double();
+        let sample = point_to_sample(&p, Coordinates::Projective);
+        let [x, y, z] = [0, 1, 2].map(|i| ark_bn254::Fq::from_value(&sample.0[i], "$").unwrap());
+        let z_inv = z.inverse().unwrap();
+        let affine = p.into_affine();
+        assert_eq!((x * z_inv, y * z_inv), (affine.x, affine.y));
+    }
+}
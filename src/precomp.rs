@@ -0,0 +1,542 @@
+use ark_ec::{
+    bls12::{self, Bls12Config},
+    bn::{self, BnConfig},
+    mnt4::{self, MNT4Config},
+    mnt6::{self, MNT6Config},
+    short_weierstrass::{SWCurveConfig, Affine, Projective},
+    AffineRepr};
+use ark_ff::{
+    BitIteratorBE, Field, Fp2, Fp3};
+
+use serde::{Deserialize, Serialize};
+
+use clap::ValueEnum;
+
+use core::fmt;
+
+use anyhow::{anyhow, bail, ensure, Result};
+
+use crate::tower::{FieldValue, FromFieldValue, ToFieldValue};
+
+/// Precomputed G1 point: its affine coordinates, plus for MNT curves the
+/// coordinates multiplied by the twist.
+#[derive(Deserialize, Serialize, Debug)]
+pub struct G1Precomputed {
+    #[serde(rename="PX")]
+    pub px: FieldValue,
+    #[serde(rename="PY")]
+    pub py: FieldValue,
+    #[serde(rename="PX_twist", default, skip_serializing_if = "Option::is_none")]
+    pub px_twist: Option<FieldValue>,
+    #[serde(rename="PY_twist", default, skip_serializing_if = "Option::is_none")]
+    pub py_twist: Option<FieldValue>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct EllCoeffs {
+    pub ell_0: FieldValue,
+    #[serde(rename="ell_VW")]
+    pub ell_vw: FieldValue,
+    #[serde(rename="ell_VV")]
+    pub ell_vv: FieldValue,
+}
+
+/// Bn/Bls12 precomputed G2 point: its affine coordinates, either as
+/// `coordinates` or as `QX`/`QY`, and the line coefficients of every Miller
+/// loop step.
+#[derive(Deserialize, Serialize, Debug)]
+pub struct EllPrecomputed {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coordinates: Option<(FieldValue, FieldValue)>,
+    #[serde(rename="QX", default, skip_serializing_if = "Option::is_none")]
+    pub qx: Option<FieldValue>,
+    #[serde(rename="QY", default, skip_serializing_if = "Option::is_none")]
+    pub qy: Option<FieldValue>,
+    #[serde(alias="coeffs")]
+    pub coefficients: Vec<EllCoeffs>,
+}
+
+/// Coefficients of one doubling step, arkworks' `AteDoubleCoefficients`.
+#[derive(Deserialize, Serialize, Debug)]
+pub struct AteDoubleCoeffs {
+    #[serde(rename="c_H")]
+    pub c_h: FieldValue,
+    #[serde(rename="c_4C")]
+    pub c_4c: FieldValue,
+    #[serde(rename="c_J")]
+    pub c_j: FieldValue,
+    #[serde(rename="c_L")]
+    pub c_l: FieldValue,
+}
+
+/// Coefficients of one addition step, arkworks' `AteAdditionCoefficients`.
+#[derive(Deserialize, Serialize, Debug)]
+pub struct AteAdditionCoeffs {
+    #[serde(rename="c_L1")]
+    pub c_l1: FieldValue,
+    #[serde(rename="c_RZ")]
+    pub c_rz: FieldValue,
+}
+
+/// MNT precomputed G2 point as libff dumps it: the affine coordinates, y
+/// squared, the coordinates divided by the twist and the coefficients of
+/// the doubling and addition steps.
+#[derive(Deserialize, Serialize, Debug)]
+pub struct AtePrecomputed {
+    #[serde(rename="QX", default, skip_serializing_if = "Option::is_none")]
+    pub qx: Option<FieldValue>,
+    #[serde(rename="QY", default, skip_serializing_if = "Option::is_none")]
+    pub qy: Option<FieldValue>,
+    #[serde(rename="QY2", default, skip_serializing_if = "Option::is_none")]
+    pub qy2: Option<FieldValue>,
+    #[serde(rename="QX_over_twist")]
+    pub qx_over_twist: FieldValue,
+    #[serde(rename="QY_over_twist")]
+    pub qy_over_twist: FieldValue,
+    pub dbl_coeffs: Vec<AteDoubleCoeffs>,
+    pub add_coeffs: Vec<AteAdditionCoeffs>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(untagged)]
+pub enum G2Precomputed {
+    Ell(EllPrecomputed),
+    Ate(AtePrecomputed),
+}
+
+impl G2Precomputed {
+    /// The coordinates, each with its key.
+    pub fn coordinates(&self) -> Option<[(&'static str, &FieldValue); 2]> {
+        let (x, y) = match self {
+            G2Precomputed::Ell(x) => match (&x.coordinates, &x.qx, &x.qy) {
+                (Some((x, y)), _, _) => return Some([("coordinates[0]", x), ("coordinates[1]", y)]),
+                (None, Some(x), Some(y)) => (x, y),
+                _ => return None,
+            },
+            G2Precomputed::Ate(x) => (x.qx.as_ref()?, x.qy.as_ref()?),
+        };
+        Some([("QX", x), ("QY", y)])
+    }
+
+    pub fn ell(&self) -> Result<&EllPrecomputed> {
+        match self {
+            G2Precomputed::Ell(x) => Ok(x),
+            G2Precomputed::Ate(_) => bail!("expected ell_0/ell_VW/ell_VV line coefficients, got MNT Ate coefficients"),
+        }
+    }
+
+    pub fn ate(&self) -> Result<&AtePrecomputed> {
+        match self {
+            G2Precomputed::Ate(x) => Ok(x),
+            G2Precomputed::Ell(_) => bail!("expected MNT Ate coefficients, got ell_0/ell_VW/ell_VV line coefficients"),
+        }
+    }
+}
+
+/// Compares a precomputed G1 entry, found at `path`, with arkworks'
+/// `G1Prepared` of `p`. When they differ, tries the usual other
+/// normalizations of `p` to tell what the external format does instead.
+pub fn check_g1_precomputed<C>(
+    label: &str,
+    path: &str,
+    x: &G1Precomputed,
+    p: &Projective<C>,
+    prepared: &Affine<C>,
+) -> Result<()>
+where
+    C: SWCurveConfig,
+    C::BaseField: FromFieldValue,
+{
+    let px = C::BaseField::from_value(&x.px, &format!("{path}.PX"))?;
+    let py = C::BaseField::from_value(&x.py, &format!("{path}.PY"))?;
+
+    let (&ex, &ey) = prepared.xy().ok_or_else(|| anyhow!("{label}: G1Prepared is the point at infinity"))?;
+    if (px, py) == (ex, ey) {
+        return Ok(());
+    }
+
+    if (px, py) == (p.x, p.y) {
+        bail!("{label}: PX/PY are the projective X/Y, not normalized by Z");
+    }
+    if let Some(z_inv) = p.z.inverse() {
+        if (px, py) == (p.x * z_inv, p.y * z_inv) {
+            bail!("{label}: PX/PY are normalized as homogeneous X/Z, Y/Z instead of affine");
+        }
+    }
+    if (px, py) == (ex, -ey) {
+        bail!("{label}: PY is the negated affine y");
+    }
+    match (px == ex, py == ey) {
+        (false, true) => bail!("{label}: PX differs from G1Prepared"),
+        (true, false) => bail!("{label}: PY differs from G1Prepared"),
+        _ => bail!("{label}: PX and PY differ from G1Prepared"),
+    }
+}
+
+/// Compares the MNT `PX_twist`/`PY_twist` of a precomputed G1 entry, when
+/// present, with arkworks' `G1Prepared` ones.
+pub fn check_g1_twist<F>(label: &str, path: &str, x: &G1Precomputed, x_twist: F, y_twist: F) -> Result<()>
+where
+    F: FromFieldValue + PartialEq,
+{
+    for (name, v, expected) in [("PX_twist", &x.px_twist, x_twist), ("PY_twist", &x.py_twist, y_twist)] {
+        let Some(v) = v else { continue };
+        let v = F::from_value(v, &format!("{path}.{name}"))?;
+        ensure!(v == expected, "{label}: {name} differs from G1Prepared");
+    }
+    Ok(())
+}
+
+/// G1 precomputation holding the coordinates of arkworks' `G1Prepared`.
+pub fn g1_precomputed<C>(prepared: &Affine<C>) -> G1Precomputed
+where
+    C: SWCurveConfig,
+    C::BaseField: ToFieldValue,
+{
+    G1Precomputed {
+        px: prepared.x.to_value(),
+        py: prepared.y.to_value(),
+        px_twist: None,
+        py_twist: None,
+    }
+}
+
+/// Affine G2 point a precomputation was made for.
+pub fn g2_precomputed_point<C>(path: &str, x: &G2Precomputed) -> Result<Affine<C>>
+where
+    C: SWCurveConfig,
+    C::BaseField: FromFieldValue,
+{
+    let [(kx, qx), (ky, qy)] = x.coordinates()
+        .ok_or_else(|| anyhow!("{path}: precomputed G2 entry has no coordinates"))?;
+    Ok(Affine::new_unchecked(
+        C::BaseField::from_value(qx, &format!("{path}.{kx}"))?,
+        C::BaseField::from_value(qy, &format!("{path}.{ky}"))?,
+    ))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Step {
+    Double,
+    Add,
+    /// additions closing the loop: the Frobenius images of Bn, the negated
+    /// point of MNT loops with a negative count
+    FinalAdd,
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Step::Double   => "doubling",
+            Step::Add      => "addition",
+            Step::FinalAdd => "final addition",
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Twist {
+    M,
+    D,
+}
+
+/// Line coefficients of one Miller loop step, as arkworks stores them in
+/// `G2Prepared::ell_coeffs`.
+pub struct EllStep<F> {
+    pub iteration: usize,
+    pub step: Step,
+    pub coeffs: (F, F, F),
+}
+
+/// Pairs every entry of a Bls12 `G2Prepared` with its Miller loop step.
+pub fn bls12_steps<P: Bls12Config>(q: Affine<P::G2Config>) -> Vec<EllStep<Fp2<P::Fp2Config>>> {
+    let mut steps = vec![];
+    for (iteration, bit) in BitIteratorBE::new(P::X).skip(1).enumerate() {
+        steps.push((iteration, Step::Double));
+        if bit {
+            steps.push((iteration, Step::Add));
+        }
+    }
+    let prepared = bls12::G2Prepared::<P>::from(q);
+    steps.into_iter().zip(prepared.ell_coeffs)
+        .map(|((iteration, step), coeffs)| EllStep { iteration, step, coeffs })
+        .collect()
+}
+
+/// Pairs every entry of a Bn `G2Prepared` with its Miller loop step.
+pub fn bn_steps<P: BnConfig>(q: Affine<P::G2Config>) -> Vec<EllStep<Fp2<P::Fp2Config>>> {
+    let mut steps = vec![];
+    for (iteration, digit) in P::ATE_LOOP_COUNT.iter().rev().skip(1).enumerate() {
+        steps.push((iteration, Step::Double));
+        if *digit != 0 {
+            steps.push((iteration, Step::Add));
+        }
+    }
+    let last = P::ATE_LOOP_COUNT.len() - 1;
+    steps.push((last, Step::FinalAdd));
+    steps.push((last, Step::FinalAdd));
+
+    let prepared = bn::G2Prepared::<P>::from(q);
+    steps.into_iter().zip(prepared.ell_coeffs)
+        .map(|((iteration, step), coeffs)| EllStep { iteration, step, coeffs })
+        .collect()
+}
+
+pub fn bls12_twist<P: Bls12Config>() -> Twist {
+    match P::TWIST_TYPE {
+        bls12::TwistType::M => Twist::M,
+        bls12::TwistType::D => Twist::D,
+    }
+}
+
+pub fn bn_twist<P: BnConfig>() -> Twist {
+    match P::TWIST_TYPE {
+        bn::TwistType::M => Twist::M,
+        bn::TwistType::D => Twist::D,
+    }
+}
+
+/// Ways external tools name arkworks' line coefficients.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Layout {
+    /// (ell_0, ell_VW, ell_VV) is arkworks' tuple as is
+    Arkworks,
+    /// ell_0 is the constant term, ell_VV the P.x term and ell_VW the P.y
+    /// term times the Fq6 non-residue, as in libff
+    Libff,
+}
+
+impl Layout {
+    fn expected<F: Field>(&self, twist: Twist, xi: F, c: &(F, F, F)) -> [F; 3] {
+        match self {
+            Layout::Arkworks => [c.0, c.1, c.2],
+            Layout::Libff => {
+                let (constant, px, py) = match twist {
+                    Twist::M => (c.0, c.1, c.2),
+                    Twist::D => (c.2, c.1, c.0),
+                };
+                [constant, py * xi, px]
+            }
+        }
+    }
+}
+
+/// G2 precomputation for `q` with arkworks' line coefficients written in
+/// `layout`.
+pub fn ell_precomputed<F>(q: (F, F), steps: &[EllStep<F>], twist: Twist, xi: F, layout: Layout) -> EllPrecomputed
+where
+    F: Field + ToFieldValue,
+{
+    EllPrecomputed {
+        coordinates: None,
+        qx: Some(q.0.to_value()),
+        qy: Some(q.1.to_value()),
+        coefficients: steps.iter()
+            .map(|step| {
+                let [ell_0, ell_vw, ell_vv] = layout.expected(twist, xi, &step.coeffs);
+                EllCoeffs {
+                    ell_0: ell_0.to_value(),
+                    ell_vw: ell_vw.to_value(),
+                    ell_vv: ell_vv.to_value(),
+                }
+            })
+            .collect(),
+    }
+}
+
+/// Compares the vector's line coefficients with arkworks' ones, detecting
+/// which layout the vector uses and pointing at the first Miller loop step
+/// that disagrees.
+pub fn check_ell_coeffs<F>(
+    label: &str,
+    path: &str,
+    x: &EllPrecomputed,
+    steps: &[EllStep<F>],
+    twist: Twist,
+    xi: F,
+) -> Result<()>
+where
+    F: Field + FromFieldValue,
+{
+    let names = ["ell_0", "ell_VW", "ell_VV"];
+    let vector = x.coefficients.iter().enumerate()
+        .map(|(i, c)| {
+            [&c.ell_0, &c.ell_vw, &c.ell_vv].into_iter().zip(names)
+                .map(|(v, name)| F::from_value(v, &format!("{path}.coefficients[{i}].{name}")))
+                .collect::<Result<Vec<_>>>()
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    ensure!(vector.len() == steps.len(),
+        "{label}: {} line coefficients, arkworks has {}", vector.len(), steps.len());
+
+    /* the layout agreeing on the most leading steps is the one the vector uses */
+    let agreeing = |layout: Layout| vector.iter().zip(steps)
+        .take_while(|(v, s)| v[..] == layout.expected(twist, xi, &s.coeffs)[..])
+        .count();
+    let (layout, n) = [Layout::Arkworks, Layout::Libff].into_iter()
+        .map(|layout| (layout, agreeing(layout)))
+        .max_by_key(|(_, n)| *n)
+        .unwrap();
+
+    if n == steps.len() {
+        return Ok(());
+    }
+
+    let step = &steps[n];
+    let expected = layout.expected(twist, xi, &step.coeffs);
+    let differing: Vec<&str> = names.iter().zip(vector[n].iter().zip(expected))
+        .filter(|(_, (v, e))| *v != e)
+        .map(|(name, _)| *name)
+        .collect();
+    bail!("{label}: layout {layout:?}, first mismatch at coefficient {n} of {}, loop iteration {} ({} step): {} differ",
+        steps.len(), step.iteration, step.step, differing.join(", "))
+}
+
+/// Contents of an MNT `G2Prepared`, with the Miller loop step of every
+/// addition.
+pub struct AtePrepared<F> {
+    pub x: F,
+    pub y: F,
+    pub x_over_twist: F,
+    pub y_over_twist: F,
+    /// c_H, c_4C, c_J, c_L of the doubling in every loop iteration
+    pub double: Vec<[F; 4]>,
+    /// c_L1, c_RZ of every addition
+    pub addition: Vec<[F; 2]>,
+    pub addition_steps: Vec<(usize, Step)>,
+}
+
+fn ate_addition_steps(loop_count: &[i8], is_neg: bool) -> Vec<(usize, Step)> {
+    let mut steps: Vec<_> = loop_count.iter().skip(1).enumerate()
+        .filter(|(_, bit)| **bit != 0)
+        .map(|(iteration, _)| (iteration, Step::Add))
+        .collect();
+    if is_neg {
+        steps.push((loop_count.len() - 1, Step::FinalAdd));
+    }
+    steps
+}
+
+pub fn mnt4_prepared<P: MNT4Config>(q: Affine<P::G2Config>) -> AtePrepared<Fp2<P::Fp2Config>> {
+    let prepared = mnt4::G2Prepared::<P>::from(q);
+    AtePrepared {
+        x: prepared.x,
+        y: prepared.y,
+        x_over_twist: prepared.x_over_twist,
+        y_over_twist: prepared.y_over_twist,
+        double: prepared.double_coefficients.iter().map(|c| [c.c_h, c.c_4c, c.c_j, c.c_l]).collect(),
+        addition: prepared.addition_coefficients.iter().map(|c| [c.c_l1, c.c_rz]).collect(),
+        addition_steps: ate_addition_steps(P::ATE_LOOP_COUNT, P::ATE_IS_LOOP_COUNT_NEG),
+    }
+}
+
+pub fn mnt6_prepared<P: MNT6Config>(q: Affine<P::G2Config>) -> AtePrepared<Fp3<P::Fp3Config>> {
+    let prepared = mnt6::G2Prepared::<P>::from(q);
+    AtePrepared {
+        x: prepared.x,
+        y: prepared.y,
+        x_over_twist: prepared.x_over_twist,
+        y_over_twist: prepared.y_over_twist,
+        double: prepared.double_coefficients.iter().map(|c| [c.c_h, c.c_4c, c.c_j, c.c_l]).collect(),
+        addition: prepared.addition_coefficients.iter().map(|c| [c.c_l1, c.c_rz]).collect(),
+        addition_steps: ate_addition_steps(P::ATE_LOOP_COUNT, P::ATE_IS_LOOP_COUNT_NEG),
+    }
+}
+
+/// MNT G2 precomputation in the libff layout.
+pub fn ate_precomputed<F>(prepared: &AtePrepared<F>) -> AtePrecomputed
+where
+    F: Field + ToFieldValue,
+{
+    AtePrecomputed {
+        qx: Some(prepared.x.to_value()),
+        qy: Some(prepared.y.to_value()),
+        qy2: Some(prepared.y.square().to_value()),
+        qx_over_twist: prepared.x_over_twist.to_value(),
+        qy_over_twist: prepared.y_over_twist.to_value(),
+        dbl_coeffs: prepared.double.iter()
+            .map(|[c_h, c_4c, c_j, c_l]| AteDoubleCoeffs {
+                c_h: c_h.to_value(),
+                c_4c: c_4c.to_value(),
+                c_j: c_j.to_value(),
+                c_l: c_l.to_value(),
+            })
+            .collect(),
+        add_coeffs: prepared.addition.iter()
+            .map(|[c_l1, c_rz]| AteAdditionCoeffs {
+                c_l1: c_l1.to_value(),
+                c_rz: c_rz.to_value(),
+            })
+            .collect(),
+    }
+}
+
+/// Index of the first entry of `vector` that differs from `expected`,
+/// together with the names of its differing coefficients.
+fn first_mismatch<F: PartialEq, const N: usize>(
+    vector: &[[F; N]],
+    expected: &[[F; N]],
+    names: [&'static str; N],
+) -> Option<(usize, Vec<&'static str>)> {
+    let n = vector.iter().zip(expected).take_while(|(v, e)| v == e).count();
+    (n < expected.len()).then(|| {
+        let differing = names.into_iter().zip(vector[n].iter().zip(&expected[n]))
+            .filter(|(_, (v, e))| v != e)
+            .map(|(name, _)| name)
+            .collect();
+        (n, differing)
+    })
+}
+
+/// Compares an MNT precomputation with arkworks' `G2Prepared`, pointing at
+/// the first doubling or addition step that disagrees.
+pub fn check_ate_coeffs<F>(label: &str, path: &str, x: &AtePrecomputed, prepared: &AtePrepared<F>) -> Result<()>
+where
+    F: Field + FromFieldValue,
+{
+    let parse = |v: &FieldValue, key: &str| F::from_value(v, &format!("{path}.{key}"));
+
+    if let Some((qx, qy)) = x.qx.as_ref().zip(x.qy.as_ref()) {
+        ensure!((parse(qx, "QX")?, parse(qy, "QY")?) == (prepared.x, prepared.y),
+            "{label}: QX/QY differ from G2Prepared");
+    }
+    if let Some(qy2) = &x.qy2 {
+        ensure!(parse(qy2, "QY2")? == prepared.y.square(), "{label}: QY2 is not QY squared");
+    }
+    ensure!(parse(&x.qx_over_twist, "QX_over_twist")? == prepared.x_over_twist,
+        "{label}: QX_over_twist differs from G2Prepared");
+    ensure!(parse(&x.qy_over_twist, "QY_over_twist")? == prepared.y_over_twist,
+        "{label}: QY_over_twist differs from G2Prepared");
+
+    let double = x.dbl_coeffs.iter().enumerate()
+        .map(|(i, c)| -> Result<[F; 4]> {Ok([
+            parse(&c.c_h,  &format!("dbl_coeffs[{i}].c_H"))?,
+            parse(&c.c_4c, &format!("dbl_coeffs[{i}].c_4C"))?,
+            parse(&c.c_j,  &format!("dbl_coeffs[{i}].c_J"))?,
+            parse(&c.c_l,  &format!("dbl_coeffs[{i}].c_L"))?,
+        ])})
+        .collect::<Result<Vec<_>>>()?;
+    let addition = x.add_coeffs.iter().enumerate()
+        .map(|(i, c)| -> Result<[F; 2]> {Ok([
+            parse(&c.c_l1, &format!("add_coeffs[{i}].c_L1"))?,
+            parse(&c.c_rz, &format!("add_coeffs[{i}].c_RZ"))?,
+        ])})
+        .collect::<Result<Vec<_>>>()?;
+
+    ensure!(double.len() == prepared.double.len(),
+        "{label}: {} doubling coefficients, arkworks has {}", double.len(), prepared.double.len());
+    ensure!(addition.len() == prepared.addition.len(),
+        "{label}: {} addition coefficients, arkworks has {}", addition.len(), prepared.addition.len());
+
+    if let Some((n, differing)) = first_mismatch(&double, &prepared.double, ["c_H", "c_4C", "c_J", "c_L"]) {
+        bail!("{label}: first mismatch at doubling coefficient {n}, loop iteration {n}: {} differ",
+            differing.join(", "))
+    }
+    if let Some((n, differing)) = first_mismatch(&addition, &prepared.addition, ["c_L1", "c_RZ"]) {
+        let (iteration, step) = prepared.addition_steps[n];
+        bail!("{label}: first mismatch at addition coefficient {n}, loop iteration {iteration} ({step} step): {} differ",
+            differing.join(", "))
+    }
+    Ok(())
+}
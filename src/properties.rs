@@ -0,0 +1,60 @@
+use ark_ec::pairing::PairingOutput;
+use ark_ff::Zero;
+use ark_std::{rand::{rngs::StdRng, SeedableRng}, UniformRand};
+
+use anyhow::ensure;
+
+use crate::curves::TestingCurve;
+use crate::report::{catch_quiet, panic_message, Outcome, Report};
+
+/// Checks the defining properties of the pairing over P1, P2 in G1, Q in G2
+/// and scalars a, b, naming the cases after `label`.
+pub fn check_properties<P: TestingCurve>(label: &str, p1: P::G1, p2: P::G1, q: P::G2, a: P::ScalarField, b: P::ScalarField, report: &mut Report) {
+    /* every case pairs with Q */
+    if !P::PAIRS_G2_IDENTITY && q.is_zero() {
+        report.note(format_args!("skipped {label}: Q is the G2 identity, which arkworks panics on for this curve"));
+        return;
+    }
+    let e = |p: P::G1, q: P::G2| P::pairing(p, q);
+    let pq = e(p1, q);
+    let one = PairingOutput::<P>::zero();
+
+    /* GT is written additively, so e(P,Q)^ab is e(P,Q)*ab */
+    report.check_eq(format!("{label} e(aP,bQ) = e(P,Q)^ab"), pq * (a * b), e(p1 * a, q * b));
+    report.check_eq(format!("{label} e(P1+P2,Q) = e(P1,Q)e(P2,Q)"), pq + e(p2, q), e(p1 + p2, q));
+    report.check_eq(format!("{label} e(P,-Q) = e(P,Q)^-1"), -pq, e(p1, -q));
+    /* a panic is recorded as a failure of the case rather than stopping
+     * the others */
+    let mut identity = |name: String, f: &dyn Fn() -> PairingOutput<P>| match catch_quiet(f) {
+        Ok(x) => { report.check_eq(name, one, x); }
+        Err(payload) => report.record(name, Outcome::Failed {
+            message: format!("arkworks panicked: {}", panic_message(&*payload)),
+        }),
+    };
+    identity(format!("{label} e(0,Q) = 1"), &|| e(P::G1::zero(), q));
+    if P::PAIRS_G2_IDENTITY {
+        identity(format!("{label} e(P,0) = 1"), &|| e(p1, P::G2::zero()));
+    } else {
+        report.note(format_args!("skipped {label} e(P,0) = 1: arkworks panics on the G2 identity for this curve"));
+    }
+    report.check(format!("{label} e(P,Q) != 1"), (|| {
+        ensure!(p1.is_zero() || q.is_zero() || pq != one, "e(P,Q) = 1 for nonzero P and Q");
+        Ok(())
+    })());
+}
+
+/// Checks the pairing properties over `rounds` sets of random points and
+/// scalars drawn from `seed`.
+pub fn test_random<P: TestingCurve>(seed: u64, rounds: usize, report: &mut Report) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    /* drawn up front, so that the rounds do not depend on the scheduling */
+    let rounds: Vec<_> = (0..rounds).map(|i| {
+        let (p1, p2) = (P::G1::rand(&mut rng), P::G1::rand(&mut rng));
+        let q = P::G2::rand(&mut rng);
+        let (a, b) = (P::ScalarField::rand(&mut rng), P::ScalarField::rand(&mut rng));
+        (i, p1, p2, q, a, b)
+    }).collect();
+    report.run_parallel(&rounds, |&(i, p1, p2, q, a, b), report| {
+        check_properties::<P>(&format!("random {i}"), p1, p2, q, a, b, report);
+    });
+}
@@ -0,0 +1,265 @@
+use core::any::Any;
+use core::fmt::{Display, Write};
+use std::cell::Cell;
+use std::collections::HashSet;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+use std::time::Instant;
+
+use rayon::prelude::*;
+use serde::Serialize;
+
+use anyhow::Result;
+
+/// Result of one named check.
+#[derive(Debug, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum Outcome {
+    Passed,
+    /// arkworks computes `expected`, the vector holds `actual`; `diff`
+    /// tells how they differ where that can be worked out
+    Mismatch {
+        expected: String,
+        actual: String,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        diff: Vec<String>,
+    },
+    /// the check failed with a diagnostic, or could not run at all
+    Failed { message: String },
+    /// the vector holds input points that are not valid group elements
+    Invalid { message: String },
+}
+
+#[derive(Debug, Serialize)]
+pub struct Case {
+    pub name: String,
+    #[serde(flatten)]
+    pub outcome: Outcome,
+    /// seconds since the previous case was recorded
+    pub time: f64,
+}
+
+impl Case {
+    pub fn passed(&self) -> bool {
+        matches!(self.outcome, Outcome::Passed)
+    }
+}
+
+/// Cases run over one vector file, in the order they were started. Cases
+/// are logged as they get recorded, so a failure does not stop the later
+/// checks, and the logs are printed once every file is done, in the order
+/// of the files, however they were scheduled.
+#[derive(Debug, Serialize)]
+pub struct Report {
+    pub file: String,
+    /// ID of the sample within a file of several samples
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sample: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub curve: Option<String>,
+    pub cases: Vec<Case>,
+    #[serde(skip)]
+    log: String,
+    #[serde(skip)]
+    last: Instant,
+}
+
+impl Report {
+    pub fn new(file: impl Into<String>) -> Self {
+        Report { file: file.into(), sample: None, curve: None, cases: vec![], log: String::new(), last: Instant::now() }
+    }
+
+    /// The file, followed by the sample ID in brackets if there is one.
+    pub fn name(&self) -> String {
+        match &self.sample {
+            Some(id) => format!("{} [{id}]", self.file),
+            None => self.file.clone(),
+        }
+    }
+
+    /// Adds a line to the log, printed along with the cases.
+    pub fn note(&mut self, line: impl Display) {
+        writeln!(self.log, "{line}").unwrap();
+    }
+
+    /// Prints the log of the cases recorded so far.
+    pub fn print(&self) {
+        print!("{}", self.log);
+    }
+
+    pub fn record(&mut self, name: impl Into<String>, outcome: Outcome) {
+        let name = name.into();
+        match &outcome {
+            Outcome::Passed => self.note(format_args!("ok      {name}")),
+            Outcome::Mismatch { expected, actual, diff } => {
+                self.note(format_args!("FAILED  {name}"));
+                self.note(format_args!("    expected: {expected}"));
+                self.note(format_args!("    actual:   {actual}"));
+                for line in diff {
+                    self.note(format_args!("    {line}"));
+                }
+            }
+            Outcome::Failed { message } => self.note(format_args!("FAILED  {name}: {message}")),
+            Outcome::Invalid { message } => self.note(format_args!("INVALID {name}: {message}")),
+        }
+        /* checks compute their values right before recording them, so the
+         * time since the previous case is the time the check took */
+        let now = Instant::now();
+        let time = (now - self.last).as_secs_f64();
+        self.last = now;
+        self.cases.push(Case { name, outcome, time });
+    }
+
+    /// Records whether the vector's `actual` value equals arkworks'
+    /// `expected` one.
+    pub fn check_eq<T: PartialEq + Display>(&mut self, name: impl Into<String>, expected: T, actual: T) -> bool {
+        let ok = expected == actual;
+        self.record(name, if ok {
+            Outcome::Passed
+        } else {
+            Outcome::Mismatch { expected: expected.to_string(), actual: actual.to_string(), diff: vec![] }
+        });
+        ok
+    }
+
+    /// Records a check that reports its own diagnostic on failure.
+    pub fn check(&mut self, name: impl Into<String>, result: Result<()>) -> bool {
+        let ok = result.is_ok();
+        self.record(name, match result {
+            Ok(()) => Outcome::Passed,
+            Err(e) => Outcome::Failed { message: format!("{e:#}") },
+        });
+        ok
+    }
+
+    /// Runs `check` over every item in parallel, each into a report of its
+    /// own, and appends their cases in the order of the items. A panic is
+    /// recorded as a failed case of its item, after the cases it recorded
+    /// before, and leaves the other items' reports alone.
+    pub fn run_parallel<T: Sync>(&mut self, items: &[T], check: impl Fn(&T, &mut Report) + Sync) {
+        let reports: Vec<Report> = items.par_iter()
+            .map(|x| {
+                let mut report = Report::new(self.file.clone());
+                if let Err(payload) = catch_quiet(|| check(x, &mut report)) {
+                    let message = format!("check panicked: {}", panic_message(&*payload));
+                    report.record("run", Outcome::Failed { message });
+                }
+                report
+            })
+            .collect();
+        for report in reports {
+            self.cases.extend(report.cases);
+            self.log += &report.log;
+        }
+        self.last = Instant::now();
+    }
+
+    pub fn failures(&self) -> impl Iterator<Item = &Case> {
+        self.cases.iter().filter(|c| !c.passed())
+    }
+
+    pub fn passed(&self) -> bool {
+        self.failures().next().is_none()
+    }
+
+    pub fn time(&self) -> f64 {
+        self.cases.iter().map(|c| c.time).sum()
+    }
+}
+
+/// The message a panic was raised with, as `panic!` and `expect` give it.
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    match (payload.downcast_ref::<&str>(), payload.downcast_ref::<String>()) {
+        (Some(s), _) => s.to_string(),
+        (_, Some(s)) => s.clone(),
+        _ => "panic without a message".to_string(),
+    }
+}
+
+thread_local! {
+    /* set while a panic is caught to be recorded, message included, as a
+     * failed case */
+    static QUIET: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Runs `f`, catching a panic without the default hook printing it.
+pub fn catch_quiet<T>(f: impl FnOnce() -> T) -> std::thread::Result<T> {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let default = panic::take_hook();
+        panic::set_hook(Box::new(move |info| if !QUIET.get() { default(info) }));
+    });
+    let quiet = QUIET.replace(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    QUIET.set(quiet);
+    result
+}
+
+#[derive(Serialize)]
+struct Summary<'a> {
+    files: usize,
+    samples: usize,
+    failed_samples: usize,
+    cases: usize,
+    failed_cases: usize,
+    reports: &'a [Report],
+}
+
+/// JSON summary of a run: totals, then every sample of every file with all
+/// its cases.
+pub fn to_json(reports: &[Report]) -> Result<String> {
+    let summary = Summary {
+        files: reports.iter().map(|r| &r.file).collect::<HashSet<_>>().len(),
+        samples: reports.len(),
+        failed_samples: reports.iter().filter(|r| !r.passed()).count(),
+        cases: reports.iter().map(|r| r.cases.len()).sum(),
+        failed_cases: reports.iter().map(|r| r.failures().count()).sum(),
+        reports,
+    };
+    Ok(serde_json::to_string_pretty(&summary)?)
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// JUnit XML of a run: one test suite per sample of a vector file, one test
+/// case per check, classed by curve.
+pub fn to_junit(reports: &[Report]) -> String {
+    let tests: usize = reports.iter().map(|r| r.cases.len()).sum();
+    let failures: usize = reports.iter().map(|r| r.failures().count()).sum();
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    writeln!(xml, "<testsuites tests=\"{tests}\" failures=\"{failures}\">").unwrap();
+    for report in reports {
+        let curve = report.curve.as_deref().unwrap_or("unknown");
+        writeln!(xml, "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" time=\"{:.6}\">",
+            xml_escape(&report.name()), report.cases.len(), report.failures().count(), report.time()).unwrap();
+        writeln!(xml, "    <properties><property name=\"curve\" value=\"{}\"/></properties>", xml_escape(curve)).unwrap();
+        for case in &report.cases {
+            write!(xml, "    <testcase name=\"{}\" classname=\"{}\" time=\"{:.6}\"",
+                xml_escape(&case.name), xml_escape(curve), case.time).unwrap();
+            match &case.outcome {
+                Outcome::Passed => xml.push_str("/>\n"),
+                Outcome::Mismatch { expected, actual, diff } => {
+                    let diff: String = diff.iter().map(|line| format!("\n{line}")).collect();
+                    writeln!(xml, ">\n      <failure message=\"mismatch\">expected: {}\nactual: {}{}</failure>\n    </testcase>",
+                        xml_escape(expected), xml_escape(actual), xml_escape(&diff)).unwrap();
+                }
+                Outcome::Failed { message } => {
+                    writeln!(xml, ">\n      <failure message=\"{}\"/>\n    </testcase>", xml_escape(message)).unwrap();
+                }
+                Outcome::Invalid { message } => {
+                    writeln!(xml, ">\n      <failure message=\"invalid input\">{}</failure>\n    </testcase>",
+                        xml_escape(message)).unwrap();
+                }
+            }
+        }
+        xml.push_str("  </testsuite>\n");
+    }
+    xml.push_str("</testsuites>\n");
+    xml
+}
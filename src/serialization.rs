@@ -0,0 +1,208 @@
+use ark_ec::{short_weierstrass::{SWCurveConfig, Affine, Projective}, CurveGroup};
+use ark_ff::{Field, PrimeField};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Compress, Validate};
+
+use clap::ValueEnum;
+use num_bigint::BigUint;
+use serde::{Deserialize, Serialize};
+
+use anyhow::{ensure, Result};
+
+use crate::curve_ops::Group;
+use crate::pairing::{Coordinates, ProjectivePoint, point_from_sample};
+use crate::report::{Outcome, Report};
+use crate::tower::{FromFieldValue, hex_bytes, to_hex};
+use crate::validate::{check_inputs, validity};
+
+/// Groups of checks run over a serialization vector.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Suite {
+    /// arkworks compressed form
+    Compressed,
+    /// arkworks uncompressed form
+    Uncompressed,
+    /// ZCash BLS12-381 forms, compressed and uncompressed
+    Zcash,
+}
+
+/// A point with its encodings as hex strings, each checked when present.
+#[derive(Deserialize, Serialize, Debug)]
+pub struct PointSample {
+    pub point: ProjectivePoint,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compressed: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uncompressed: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub zcash_compressed: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub zcash_uncompressed: Option<String>,
+}
+
+/* curve and group are not detected, they come from the file or the
+ * command line */
+#[derive(Deserialize, Serialize, Debug)]
+pub struct TestSample {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub curve: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<Group>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coordinates: Option<Coordinates>,
+    pub points: Vec<PointSample>,
+}
+
+/// arkworks' `CanonicalSerialize` form of `p`.
+pub fn arkworks_bytes<C: SWCurveConfig>(p: &Projective<C>, compress: Compress) -> Vec<u8> {
+    let mut bytes = vec![];
+    p.into_affine().serialize_with_mode(&mut bytes, compress).expect("writing to a vector does not fail");
+    bytes
+}
+
+/// ZCash form of `p`: its big-endian coordinates, those over Fq2 c1 first,
+/// with the compression, infinity and sort flags in the three top bits of
+/// the first byte. The sort flag tells whether y is the larger of y and -y.
+pub fn zcash_bytes<C: SWCurveConfig>(p: &Projective<C>, compress: Compress) -> Vec<u8> {
+    let size = (<C::BaseField as Field>::BasePrimeField::MODULUS_BIT_SIZE as usize).div_ceil(8);
+    /* most significant coefficient first, which is also the order they
+     * compare in */
+    let coefficients = |x: &C::BaseField| -> Vec<BigUint> {
+        let mut c: Vec<BigUint> = x.to_base_prime_field_elements().map(Into::into).collect();
+        c.reverse();
+        c
+    };
+    let coordinates = match compress {
+        Compress::Yes => 1,
+        Compress::No => 2,
+    };
+    let mut bytes = vec![0; size * C::BaseField::extension_degree() as usize * coordinates];
+
+    let p = p.into_affine();
+    if !p.infinity {
+        let values = [p.x, p.y];
+        let coefficients = values[..coordinates].iter().flat_map(coefficients);
+        for (chunk, c) in bytes.chunks_mut(size).zip(coefficients) {
+            let c = c.to_bytes_be();
+            chunk[size - c.len()..].copy_from_slice(&c);
+        }
+    }
+    let flags = match (compress, p.infinity) {
+        (Compress::Yes, true) => 0b110,
+        (Compress::Yes, false) if coefficients(&p.y) > coefficients(&-p.y) => 0b101,
+        (Compress::Yes, false) => 0b100,
+        (Compress::No, true) => 0b010,
+        (Compress::No, false) => 0b000,
+    };
+    bytes[0] |= flags << 5;
+    bytes
+}
+
+/// Checks the encodings of the points of a sample of `C` against arkworks,
+/// both ways. ZCash ones are only defined when `zcash` says the curve is
+/// BLS12-381, whose arkworks serialization is the ZCash one.
+pub fn test_sample<C>(sample: &TestSample, suites: &[Suite], zcash: bool, report: &mut Report) -> Result<()>
+where
+    C: SWCurveConfig,
+    C::BaseField: FromFieldValue,
+{
+    let enabled = |s: Suite| suites.is_empty() || suites.contains(&s);
+    let coords = sample.coordinates.unwrap_or_default();
+    ensure!(zcash || sample.points.iter().all(|x| x.zcash_compressed.is_none() && x.zcash_uncompressed.is_none()),
+        "ZCash encodings are defined for BLS12-381 only");
+
+    let points = sample.points.iter().enumerate()
+        .map(|(i, x)| point_from_sample::<C>(&x.point, coords, &format!("$.points[{i}].point")))
+        .collect::<Result<Vec<_>>>()?;
+    check_inputs(report, points.iter().enumerate()
+        .map(|(i, p)| (format!("$.points[{i}].point"), validity(p))));
+
+    for (i, (x, p)) in sample.points.iter().zip(&points).enumerate() {
+        let forms = [
+            ("compressed", &x.compressed, Suite::Compressed, Compress::Yes),
+            ("uncompressed", &x.uncompressed, Suite::Uncompressed, Compress::No),
+            ("zcash_compressed", &x.zcash_compressed, Suite::Zcash, Compress::Yes),
+            ("zcash_uncompressed", &x.zcash_uncompressed, Suite::Zcash, Compress::No),
+        ];
+        for (name, hex, suite, compress) in forms {
+            let Some(hex) = hex.as_ref().filter(|_| enabled(suite)) else { continue };
+            let bytes = hex_bytes(hex, &format!("$.points[{i}].{name}"))?;
+
+            let expected = match suite {
+                Suite::Zcash => zcash_bytes(p, compress),
+                _ => arkworks_bytes(p, compress),
+            };
+            report.check_eq(format!("points[{i}] {name} encode"), to_hex(&expected), to_hex(&bytes));
+
+            let mut reader = &bytes[..];
+            let decoded = Affine::<C>::deserialize_with_mode(&mut reader, compress, Validate::Yes);
+            report.record(format!("points[{i}] {name} decode"), match decoded {
+                Ok(_) if !reader.is_empty() => Outcome::Failed {
+                    message: format!("{} trailing bytes", reader.len()),
+                },
+                Ok(q) if Projective::from(q) == *p => Outcome::Passed,
+                Ok(q) => Outcome::Mismatch { expected: q.to_string(), actual: p.to_string(), diff: vec![] },
+                Err(e) => Outcome::Failed { message: format!("arkworks rejects the bytes: {e}") },
+            });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_bls12_381::{g1, g2};
+    use ark_ec::Group;
+    use ark_ff::Zero;
+    use ark_std::{rand::{rngs::StdRng, SeedableRng}, UniformRand};
+
+    /* the published ZCash encodings of the BLS12-381 generators */
+    const G1_COMPRESSED: &str = "97f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb";
+    const G1_UNCOMPRESSED: &str = "17f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb\
+        08b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1";
+    const G2_COMPRESSED: &str = "93e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e\
+        024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8";
+
+    #[test]
+    fn zcash_generators() {
+        let g1 = Projective::<g1::Config>::generator();
+        let g2 = Projective::<g2::Config>::generator();
+        assert_eq!(to_hex(&zcash_bytes(&g1, Compress::Yes)), G1_COMPRESSED);
+        assert_eq!(to_hex(&zcash_bytes(&g1, Compress::No)), G1_UNCOMPRESSED);
+        assert_eq!(to_hex(&zcash_bytes(&g2, Compress::Yes)), G2_COMPRESSED);
+    }
+
+    /* the generators have the smaller y, so their negations set the sort
+     * flag, 0x20 */
+    #[test]
+    fn zcash_sort_flag() {
+        let g1 = Projective::<g1::Config>::generator();
+        let g2 = Projective::<g2::Config>::generator();
+        assert_eq!(to_hex(&zcash_bytes(&-g1, Compress::Yes)), format!("b7{}", &G1_COMPRESSED[2..]));
+        assert_eq!(to_hex(&zcash_bytes(&-g2, Compress::Yes)), format!("b3{}", &G2_COMPRESSED[2..]));
+        /* uncompressed forms carry y, and no sort flag */
+        assert_eq!(zcash_bytes(&-g1, Compress::No)[0] >> 5, 0);
+    }
+
+    #[test]
+    fn zcash_infinity() {
+        let zero = Projective::<g1::Config>::zero();
+        assert_eq!(to_hex(&zcash_bytes(&zero, Compress::Yes)), format!("c0{}", "00".repeat(47)));
+        assert_eq!(to_hex(&zcash_bytes(&zero, Compress::No)), format!("40{}", "00".repeat(95)));
+        let zero = Projective::<g2::Config>::zero();
+        assert_eq!(to_hex(&zcash_bytes(&zero, Compress::Yes)), format!("c0{}", "00".repeat(95)));
+    }
+
+    /* arkworks serializes BLS12-381 in the ZCash form */
+    #[test]
+    fn zcash_is_arkworks() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..8 {
+            let (p, q) = (Projective::<g1::Config>::rand(&mut rng), Projective::<g2::Config>::rand(&mut rng));
+            for compress in [Compress::Yes, Compress::No] {
+                assert_eq!(zcash_bytes(&p, compress), arkworks_bytes(&p, compress));
+                assert_eq!(zcash_bytes(&q, compress), arkworks_bytes(&q, compress));
+            }
+        }
+    }
+}
@@ -0,0 +1,349 @@
+use ark_ff::{BigInteger, CubicExtConfig, CubicExtField, Fp, FpConfig, PrimeField, QuadExtConfig, QuadExtField};
+
+use clap::ValueEnum;
+use num_bigint::BigUint;
+use serde::{Deserialize, Serialize};
+
+use anyhow::{anyhow, bail, ensure, Result};
+
+use std::cell::Cell;
+
+/// How the prime field coefficients of a vector are written.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+#[value(rename_all = "snake_case")]
+pub enum Encoding {
+    /// decimal integer, or hex with a 0x prefix
+    #[default]
+    Decimal,
+    /// hex integer, 0x prefix optional
+    Hex,
+    /// hex dump of the limbs, least significant byte first
+    Le,
+    /// hex dump of the limbs, most significant byte first
+    Be,
+    /// `Le` dump of the Montgomery form a*R
+    MontgomeryLe,
+    /// `Be` dump of the Montgomery form a*R
+    MontgomeryBe,
+}
+
+impl Encoding {
+    /// Decodes `s` into an element of `F`, which must be in canonical form,
+    /// below the modulus.
+    fn decode<F: PrimeField>(&self, s: &str, path: &str) -> Result<F> {
+        let hex = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X"));
+        let bytes = || -> Result<Vec<u8>> {
+            /* a dump covers all the limbs, 8 bytes each */
+            let n = F::BigInt::NUM_LIMBS * 8;
+            let bytes = hex_bytes(s, path)?;
+            ensure!(bytes.len() == n, "{path}: expected a dump of {n} bytes, got {}", bytes.len());
+            Ok(bytes)
+        };
+        let n = match self {
+            Encoding::Decimal if hex.is_none() => BigUint::parse_bytes(s.as_bytes(), 10),
+            Encoding::Decimal | Encoding::Hex => BigUint::parse_bytes(hex.unwrap_or(s).as_bytes(), 16),
+            Encoding::Le | Encoding::MontgomeryLe => Some(BigUint::from_bytes_le(&bytes()?)),
+            Encoding::Be | Encoding::MontgomeryBe => Some(BigUint::from_bytes_be(&bytes()?)),
+        }.ok_or_else(|| anyhow!("{path}: failed to parse '{s}'"))?;
+
+        let x = F::BigInt::try_from(n).ok().and_then(F::from_bigint)
+            .ok_or_else(|| anyhow!("{path}: '{s}' is not below the modulus"))?;
+        Ok(match self {
+            /* R is 2^64 for every limb */
+            Encoding::MontgomeryLe | Encoding::MontgomeryBe => {
+                let r = F::from(2u64).pow([64 * F::BigInt::NUM_LIMBS as u64]);
+                x * r.inverse().expect("R is nonzero")
+            }
+            _ => x,
+        })
+    }
+}
+
+/// Reads a string of hex digits, 0x prefix optional, as bytes.
+pub fn hex_bytes(s: &str, path: &str) -> Result<Vec<u8>> {
+    let hex = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")).unwrap_or(s);
+    ensure!(hex.len().is_multiple_of(2), "{path}: odd number of hex digits in '{s}'");
+    hex.as_bytes().chunks(2)
+        .map(|d| std::str::from_utf8(d).ok().and_then(|d| u8::from_str_radix(d, 16).ok()))
+        .collect::<Option<_>>()
+        .ok_or_else(|| anyhow!("{path}: failed to parse '{s}'"))
+}
+
+/// Writes bytes as a string of hex digits, without prefix.
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/* encoding of the values that do not name one; serde passes no context
+ * down to the values, and a sample is deserialized on a single thread */
+thread_local! {
+    static ENCODING: Cell<Encoding> = const { Cell::new(Encoding::Decimal) };
+}
+
+/// Runs `f` with the values deserialized meanwhile on this thread taken to
+/// be in `encoding`, unless they declare one of their own.
+pub fn with_encoding<T>(encoding: Encoding, f: impl FnOnce() -> T) -> T {
+    /* restores the previous encoding even if `f` panics */
+    struct Restore(Encoding);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            ENCODING.with(|e| e.set(self.0));
+        }
+    }
+    let _restore = Restore(ENCODING.with(|e| e.replace(encoding)));
+    f()
+}
+
+/// A field element as it appears in the vector files: a string for a prime
+/// field element, and for an extension field either the nested list of its
+/// coefficients over the base field, or the flat list of all its prime
+/// field coefficients, `c0` first. Lists of one element are unwrapped, as
+/// some of the tools dump `[[c0, c1]]` for an Fq2 value. Any value can be
+/// written `{"encoding": ..., "value": ...}` to give the encoding of its
+/// strings.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(from = "RawFieldValue", into = "RawFieldValue")]
+pub enum FieldValue {
+    Scalar(String, Encoding),
+    List(Vec<FieldValue>),
+}
+
+/* the value as written, its strings getting their encoding on conversion */
+#[derive(Deserialize, Serialize)]
+#[serde(untagged)]
+enum RawFieldValue {
+    Scalar(String),
+    List(Vec<FieldValue>),
+    Encoded { encoding: Encoding, value: Box<FieldValue> },
+}
+
+impl FieldValue {
+    fn encoded(self, encoding: Encoding) -> Self {
+        match self {
+            FieldValue::Scalar(s, _) => FieldValue::Scalar(s, encoding),
+            FieldValue::List(l) => FieldValue::List(l.into_iter().map(|x| x.encoded(encoding)).collect()),
+        }
+    }
+}
+
+impl From<RawFieldValue> for FieldValue {
+    fn from(x: RawFieldValue) -> Self {
+        match x {
+            RawFieldValue::Scalar(s) => FieldValue::Scalar(s, ENCODING.with(Cell::get)),
+            RawFieldValue::List(l) => FieldValue::List(l),
+            RawFieldValue::Encoded { encoding, value } => value.encoded(encoding),
+        }
+    }
+}
+
+impl From<FieldValue> for RawFieldValue {
+    fn from(x: FieldValue) -> Self {
+        match x {
+            FieldValue::Scalar(s, Encoding::Decimal) => RawFieldValue::Scalar(s),
+            FieldValue::Scalar(s, encoding) => RawFieldValue::Encoded {
+                encoding,
+                value: Box::new(FieldValue::Scalar(s, Encoding::Decimal)),
+            },
+            FieldValue::List(l) => RawFieldValue::List(l),
+        }
+    }
+}
+
+/// Fields that can be built from a [`FieldValue`]. Errors name the JSON
+/// path of the offending value, as passed in `path`.
+pub trait FromFieldValue: Sized {
+    /// Degree over the prime field.
+    const DEGREE: usize;
+
+    fn from_value(x: &FieldValue, path: &str) -> Result<Self>;
+
+    /// Builds the element from the `DEGREE` prime field coefficients found
+    /// at `offset` of the flat list at `path`.
+    fn from_flat(coeffs: &[FieldValue], path: &str, offset: usize) -> Result<Self>;
+}
+
+impl<P: FpConfig<N>, const N: usize> FromFieldValue for Fp<P, N> {
+    const DEGREE: usize = 1;
+
+    fn from_value(x: &FieldValue, path: &str) -> Result<Self> {
+        match x {
+            FieldValue::Scalar(s, encoding) => encoding.decode(s, path),
+            FieldValue::List(l) if l.len() == 1 => Self::from_value(&l[0], &format!("{path}[0]")),
+            FieldValue::List(l) => bail!("{path}: expected a prime field element, got a list of {}", l.len()),
+        }
+    }
+
+    fn from_flat(coeffs: &[FieldValue], path: &str, offset: usize) -> Result<Self> {
+        Self::from_value(&coeffs[offset], &format!("{path}[{offset}]"))
+    }
+}
+
+/// Fields written as nested [`FieldValue`] lists, the way the vector files
+/// hold them.
+pub trait ToFieldValue {
+    fn to_value(&self) -> FieldValue;
+}
+
+impl<P: FpConfig<N>, const N: usize> ToFieldValue for Fp<P, N> {
+    fn to_value(&self) -> FieldValue {
+        FieldValue::Scalar(self.into_bigint().to_string(), Encoding::Decimal)
+    }
+}
+
+impl<Q> ToFieldValue for QuadExtField<Q>
+where
+    Q: QuadExtConfig,
+    Q::BaseField: ToFieldValue,
+{
+    fn to_value(&self) -> FieldValue {
+        FieldValue::List(vec![self.c0.to_value(), self.c1.to_value()])
+    }
+}
+
+impl<Q> ToFieldValue for CubicExtField<Q>
+where
+    Q: CubicExtConfig,
+    Q::BaseField: ToFieldValue,
+{
+    fn to_value(&self) -> FieldValue {
+        FieldValue::List(vec![self.c0.to_value(), self.c1.to_value(), self.c2.to_value()])
+    }
+}
+
+/// Reads the `k` coefficients of an extension field element over `B`.
+fn coefficients<B: FromFieldValue>(x: &FieldValue, path: &str, k: usize) -> Result<Vec<B>> {
+    match x {
+        FieldValue::List(l) if l.len() == k => l.iter().enumerate()
+            .map(|(i, c)| B::from_value(c, &format!("{path}[{i}]")))
+            .collect(),
+        FieldValue::List(l) if l.len() == k * B::DEGREE => flat_coefficients(l, path, 0, k),
+        FieldValue::List(l) if l.len() == 1 => coefficients(&l[0], &format!("{path}[0]"), k),
+        FieldValue::List(l) => bail!("{path}: expected {k} coefficients or a flat list of {}, got {}",
+            k * B::DEGREE, l.len()),
+        FieldValue::Scalar(s, _) => bail!("{path}: expected a list of {k} coefficients, got '{s}'"),
+    }
+}
+
+fn flat_coefficients<B: FromFieldValue>(coeffs: &[FieldValue], path: &str, offset: usize, k: usize) -> Result<Vec<B>> {
+    (0..k).map(|i| B::from_flat(coeffs, path, offset + i * B::DEGREE)).collect()
+}
+
+impl<Q> FromFieldValue for QuadExtField<Q>
+where
+    Q: QuadExtConfig,
+    Q::BaseField: FromFieldValue,
+{
+    const DEGREE: usize = 2 * Q::BaseField::DEGREE;
+
+    fn from_value(x: &FieldValue, path: &str) -> Result<Self> {
+        let c = coefficients(x, path, 2)?;
+        Ok(QuadExtField::new(c[0], c[1]))
+    }
+
+    fn from_flat(coeffs: &[FieldValue], path: &str, offset: usize) -> Result<Self> {
+        let c = flat_coefficients(coeffs, path, offset, 2)?;
+        Ok(QuadExtField::new(c[0], c[1]))
+    }
+}
+
+impl<Q> FromFieldValue for CubicExtField<Q>
+where
+    Q: CubicExtConfig,
+    Q::BaseField: FromFieldValue,
+{
+    const DEGREE: usize = 3 * Q::BaseField::DEGREE;
+
+    fn from_value(x: &FieldValue, path: &str) -> Result<Self> {
+        let c = coefficients(x, path, 3)?;
+        Ok(CubicExtField::new(c[0], c[1], c[2]))
+    }
+
+    fn from_flat(coeffs: &[FieldValue], path: &str, offset: usize) -> Result<Self> {
+        let c = flat_coefficients(coeffs, path, offset, 3)?;
+        Ok(CubicExtField::new(c[0], c[1], c[2]))
+    }
+}
+
+/// An integer constant, a scalar or an exponent: a JSON number, or a decimal
+/// or `0x` hex string of any width.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum Constant {
+    Small(u64),
+    Wide(String),
+}
+
+impl Constant {
+    pub fn to_biguint(&self, path: &str) -> Result<BigUint> {
+        Ok(match self {
+            Constant::Small(n) => BigUint::from(*n),
+            Constant::Wide(s) => match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+                Some(hex) => BigUint::parse_bytes(hex.as_bytes(), 16),
+                None => BigUint::parse_bytes(s.as_bytes(), 10),
+            }.ok_or_else(|| anyhow!("{path}: failed to parse '{s}'"))?,
+        })
+    }
+
+    /// The constant reduced modulo the order of `F`.
+    pub fn to_field<F: PrimeField>(&self, path: &str) -> Result<F> {
+        Ok(F::from_le_bytes_mod_order(&self.to_biguint(path)?.to_bytes_le()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_ff::{Field, One};
+
+    /* the dumps are made from arkworks' own limbs, an Fp holding its
+     * Montgomery form */
+    fn round_trip<P: FpConfig<N>, const N: usize>(x: Fp<P, N>) {
+        let n = x.into_bigint();
+        let encodings = [
+            (Encoding::Decimal, n.to_string()),
+            (Encoding::Decimal, format!("0x{}", to_hex(&n.to_bytes_be()))),
+            (Encoding::Hex, to_hex(&n.to_bytes_be())),
+            (Encoding::Le, to_hex(&n.to_bytes_le())),
+            (Encoding::Be, format!("0x{}", to_hex(&n.to_bytes_be()))),
+            (Encoding::MontgomeryLe, to_hex(&x.0.to_bytes_le())),
+            (Encoding::MontgomeryBe, to_hex(&x.0.to_bytes_be())),
+        ];
+        for (encoding, s) in encodings {
+            assert_eq!(encoding.decode::<Fp<P, N>>(&s, "$").unwrap(), x, "{encoding:?} {s}");
+        }
+    }
+
+    #[test]
+    fn bn254_round_trip() {
+        round_trip(-ark_bn254::Fq::from(123456789u64));
+        round_trip(ark_bn254::Fr::from(2u64).inverse().unwrap());
+    }
+
+    #[test]
+    fn bls12_381_round_trip() {
+        round_trip(-ark_bls12_381::Fq::from(123456789u64));
+        round_trip(ark_bls12_381::Fr::from(2u64).inverse().unwrap());
+    }
+
+    /* the Montgomery form of one is R mod p, written out independently of
+     * arkworks */
+    #[test]
+    fn montgomery_one() {
+        let bn254 = "0e0a77c19a07df2f666ea36f7879462c0a78eb28f5c70b3dd35d438dc58f0d9d";
+        let bls12_381 = "15f65ec3fa80e4935c071a97a256ec6d77ce5853705257455f48985753c758baebf4000bc40c0002760900000002fffd";
+        assert!(Encoding::MontgomeryBe.decode::<ark_bn254::Fq>(bn254, "$").unwrap().is_one());
+        assert!(Encoding::MontgomeryBe.decode::<ark_bls12_381::Fq>(bls12_381, "$").unwrap().is_one());
+
+        let mut le = hex_bytes(bn254, "$").unwrap();
+        le.reverse();
+        assert!(Encoding::MontgomeryLe.decode::<ark_bn254::Fq>(&to_hex(&le), "$").unwrap().is_one());
+    }
+
+    #[test]
+    fn dump_length() {
+        assert!(Encoding::Le.decode::<ark_bn254::Fq>("01", "$").is_err());
+        assert!(Encoding::Le.decode::<ark_bn254::Fq>(&format!("01{}", "00".repeat(31)), "$").unwrap().is_one());
+        assert!(Encoding::Be.decode::<ark_bn254::Fq>(&format!("{}01", "00".repeat(31)), "$").unwrap().is_one());
+    }
+}
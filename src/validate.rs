@@ -0,0 +1,92 @@
+use core::fmt;
+
+use ark_ec::{short_weierstrass::{SWCurveConfig, Projective}, CurveGroup};
+
+use serde::{Deserialize, Serialize};
+
+use anyhow::Result;
+
+use crate::curve_ops::Group;
+use crate::pairing::{Coordinates, ProjectivePoint, point_from_sample};
+use crate::report::{Outcome, Report};
+use crate::tower::FromFieldValue;
+
+/// Whether a point is a valid element of its group, and if not, why.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Validity {
+    Valid,
+    NotOnCurve,
+    NotInSubgroup,
+}
+
+impl fmt::Display for Validity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Validity::Valid => "valid",
+            Validity::NotOnCurve => "not on the curve",
+            Validity::NotInSubgroup => "not in the prime order subgroup",
+        })
+    }
+}
+
+pub fn validity<C: SWCurveConfig>(p: &Projective<C>) -> Validity {
+    let p = p.into_affine();
+    if !p.is_on_curve() {
+        Validity::NotOnCurve
+    } else if !p.is_in_correct_subgroup_assuming_on_curve() {
+        Validity::NotInSubgroup
+    } else {
+        Validity::Valid
+    }
+}
+
+/// Records the case "input points", invalid when any of the `points`,
+/// given by path and validity, is not a valid group element. Checks on
+/// invalid inputs still run, so their failures are not arithmetic ones.
+pub fn check_inputs(report: &mut Report, points: impl IntoIterator<Item = (String, Validity)>) {
+    let invalid: Vec<String> = points.into_iter()
+        .filter(|(_, v)| *v != Validity::Valid)
+        .map(|(path, v)| format!("{path} is {v}"))
+        .collect();
+    report.record("input points", if invalid.is_empty() {
+        Outcome::Passed
+    } else {
+        Outcome::Invalid { message: invalid.join(", ") }
+    });
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct PointSample {
+    pub point: ProjectivePoint,
+    /// what the implementation under test takes the point for
+    pub expected: Validity,
+}
+
+/* curve and group cannot be detected from points that are meant to be
+ * invalid, they come from the file or the command line */
+#[derive(Deserialize, Serialize, Debug)]
+pub struct TestSample {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub curve: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<Group>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coordinates: Option<Coordinates>,
+    pub points: Vec<PointSample>,
+}
+
+/// Checks that arkworks accepts and rejects the points of a sample of `C`
+/// the way the sample expects.
+pub fn test_sample<C>(sample: &TestSample, report: &mut Report) -> Result<()>
+where
+    C: SWCurveConfig,
+    C::BaseField: FromFieldValue,
+{
+    let coords = sample.coordinates.unwrap_or_default();
+    for (i, x) in sample.points.iter().enumerate() {
+        let p = point_from_sample::<C>(&x.point, coords, &format!("$.points[{i}].point"))?;
+        report.check_eq(format!("points[{i}] {}", x.expected), validity(&p), x.expected);
+    }
+    Ok(())
+}